@@ -0,0 +1,152 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::collections::BTreeSet;
+use xor_name::{k_closest, Prefix, XorName};
+
+fn cmp_distance(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let target: XorName = rng.gen();
+    let lhs: XorName = rng.gen();
+    // Shares the first 20 bytes with `lhs`, so the comparison has to look past the first word.
+    let mut rhs = lhs;
+    rhs.0[20] ^= 1;
+
+    let _ = c.bench_function("cmp_distance", |b| {
+        b.iter(|| black_box(&target).cmp_distance(black_box(&lhs), black_box(&rhs)))
+    });
+
+    let names: Vec<XorName> = (0..1000).map(|_| rng.gen()).collect();
+    let set: BTreeSet<XorName> = names.iter().copied().collect();
+    let _ = c.bench_function("k_closest_20_of_1000", |b| {
+        b.iter(|| k_closest(black_box(&set), black_box(&target), 20))
+    });
+    let _ = c.bench_function("sort_1000_by_distance", |b| {
+        b.iter_batched(
+            || names.clone(),
+            |mut names| names.sort_by(|lhs, rhs| target.cmp_distance(lhs, rhs)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn common_prefix(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let name: XorName = rng.gen();
+    let prefix = Prefix::new(240, name);
+    let mut other = name;
+    other.0[29] ^= 0b0001_0000;
+
+    let _ = c.bench_function("common_prefix", |b| {
+        b.iter(|| black_box(&prefix).common_prefix(black_box(&other)))
+    });
+}
+
+fn prefix_matches(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let prefix = Prefix::new(37, rng.gen());
+    let inside = prefix.substituted_in(rng.gen());
+    let outside = prefix.sibling().substituted_in(rng.gen());
+
+    let _ = c.bench_function("prefix_matches_inside", |b| {
+        b.iter(|| black_box(&prefix).matches(black_box(&inside)))
+    });
+    let _ = c.bench_function("prefix_matches_outside", |b| {
+        b.iter(|| black_box(&prefix).matches(black_box(&outside)))
+    });
+
+    let names: Vec<XorName> = (0..1000).map(|_| rng.gen()).collect();
+    let short = Prefix::new(3, rng.gen());
+    let _ = c.bench_function("prefix_filter_1000", |b| {
+        b.iter(|| {
+            black_box(&names)
+                .iter()
+                .filter(|name| short.matches(name))
+                .count()
+        })
+    });
+    let _ = c.bench_function("prefix_partition_1000", |b| {
+        b.iter(|| black_box(&short).partition(black_box(&names)))
+    });
+    let _ = c.bench_function("prefix_matches_many_1000", |b| {
+        b.iter(|| black_box(&short).matches_many(black_box(&names)))
+    });
+}
+
+fn prefix_ord(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let prefixes: Vec<Prefix> = (0..1000)
+        .map(|_| Prefix::new(rng.gen_range(0..24), rng.gen()))
+        .collect();
+
+    let _ = c.bench_function("prefix_sort_1000", |b| {
+        b.iter_batched(
+            || prefixes.clone(),
+            |mut prefixes| prefixes.sort(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let set: std::collections::BTreeSet<Prefix> = prefixes.iter().copied().collect();
+    let _ = c.bench_function("prefix_btree_lookup_1000", |b| {
+        b.iter(|| {
+            prefixes
+                .iter()
+                .filter(|prefix| black_box(&set).contains(prefix))
+                .count()
+        })
+    });
+}
+
+fn substituted_in(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let name: XorName = rng.gen();
+
+    for bit_count in [5, 37, 240] {
+        let prefix = Prefix::new(bit_count, rng.gen());
+        let _ = c.bench_function(&format!("substituted_in_{}_bits", bit_count), |b| {
+            b.iter(|| black_box(&prefix).substituted_in(black_box(name)))
+        });
+    }
+}
+
+fn is_covered_by(c: &mut Criterion) {
+    // Worst case for coverage checks: the whole namespace split into 2^10 equal prefixes, with
+    // the last one missing so that every prefix needs to be examined.
+    let depth = 10;
+    let mut prefixes = vec![Prefix::default()];
+    for _ in 0..depth {
+        prefixes = prefixes
+            .into_iter()
+            .flat_map(|prefix| vec![prefix.pushed(false), prefix.pushed(true)])
+            .collect();
+    }
+    let complete = prefixes.clone();
+    let _ = prefixes.pop();
+
+    let _ = c.bench_function("is_covered_by_complete_depth_10", |b| {
+        b.iter(|| Prefix::default().is_covered_by(black_box(&complete)))
+    });
+    let _ = c.bench_function("is_covered_by_incomplete_depth_10", |b| {
+        b.iter(|| Prefix::default().is_covered_by(black_box(&prefixes)))
+    });
+}
+
+criterion_group!(
+    benches,
+    cmp_distance,
+    common_prefix,
+    prefix_matches,
+    prefix_ord,
+    substituted_in,
+    is_covered_by
+);
+criterion_main!(benches);
@@ -0,0 +1,76 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Deserializes names and prefixes from untrusted bytes and strings, in binary and human
+//! readable formats, and checks that whatever is accepted serializes back to an equal value.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeSet, fmt::Debug};
+use xor_name::{Prefix, XorName};
+
+#[derive(Arbitrary, Debug)]
+enum Format {
+    Bincode(Vec<u8>),
+    Json(String),
+}
+
+#[derive(Arbitrary, Debug)]
+enum Target {
+    XorName,
+    Prefix,
+    // A struct with a field for each of the `serde_helpers` modules.
+    Helpers,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Helpers {
+    #[serde(with = "xor_name::serde_helpers::hex")]
+    hex: XorName,
+    #[serde(with = "xor_name::serde_helpers::bytes")]
+    bytes: XorName,
+    #[serde(with = "xor_name::serde_helpers::option")]
+    option: Option<XorName>,
+    #[serde(with = "xor_name::serde_helpers::vec")]
+    vec: Vec<XorName>,
+    #[serde(with = "xor_name::serde_helpers::btree_set")]
+    btree_set: BTreeSet<XorName>,
+}
+
+fn round_trip<T>(format: &Format)
+where
+    T: Debug + PartialEq + Serialize + DeserializeOwned,
+{
+    match format {
+        Format::Bincode(bytes) => {
+            if let Ok(value) = bincode::deserialize::<T>(bytes) {
+                let bytes = bincode::serialize(&value).unwrap();
+                assert_eq!(bincode::deserialize::<T>(&bytes).unwrap(), value);
+            }
+        }
+        Format::Json(json) => {
+            if let Ok(value) = serde_json::from_str::<T>(json) {
+                let json = serde_json::to_string(&value).unwrap();
+                assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+            }
+        }
+    }
+}
+
+fuzz_target!(|input: (Target, Format)| {
+    let (target, format) = input;
+    match target {
+        Target::XorName => round_trip::<XorName>(&format),
+        Target::Prefix => round_trip::<Prefix>(&format),
+        Target::Helpers => round_trip::<Helpers>(&format),
+    }
+});
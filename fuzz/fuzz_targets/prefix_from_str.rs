@@ -0,0 +1,65 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Parses prefixes from strings, mostly of valid characters, and checks that valid ones
+//! round-trip through both `Display` formats.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use xor_name::{Error, Prefix, XOR_NAME_LEN};
+
+#[derive(Arbitrary, Debug)]
+enum Char {
+    Zero,
+    One,
+    Separator,
+    Other(char),
+}
+
+#[derive(Arbitrary, Debug)]
+enum Input {
+    // Mostly valid strings, including overlong ones.
+    Chars(Vec<Char>),
+    Raw(String),
+}
+
+fuzz_target!(|input: Input| {
+    let bits: String = match input {
+        Input::Chars(chars) => chars
+            .into_iter()
+            .map(|c| match c {
+                Char::Zero => '0',
+                Char::One => '1',
+                Char::Separator => '_',
+                Char::Other(c) => c,
+            })
+            .collect(),
+        Input::Raw(raw) => raw,
+    };
+    let bit_count = bits.chars().filter(|c| *c != '_').count();
+    let invalid = bits.chars().find(|c| !matches!(c, '0' | '1' | '_'));
+
+    match bits.parse::<Prefix>() {
+        Ok(prefix) => {
+            assert!(invalid.is_none());
+            assert_eq!(prefix.bit_count(), bit_count);
+            assert_eq!(prefix.to_string().parse::<Prefix>(), Ok(prefix));
+            assert_eq!(format!("{:#}", prefix).parse::<Prefix>(), Ok(prefix));
+        }
+        Err(Error::InvalidChar(c)) => assert_eq!(Some(c), invalid),
+        Err(Error::TooLong(len)) => {
+            assert!(invalid.is_none());
+            assert_eq!(len, bit_count);
+            assert!(len > 8 * XOR_NAME_LEN);
+        }
+        Err(error) => panic!("unexpected error: {}", error),
+    }
+});
@@ -0,0 +1,47 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Parses names from strings, mostly of hex digits, and checks that valid ones round-trip
+//! through both hex formats.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use xor_name::{Error, XorName, XOR_NAME_LEN};
+
+#[derive(Arbitrary, Debug)]
+enum Input {
+    // Mostly valid strings, with lengths around the expected 64 digits.
+    Digits(Vec<u8>),
+    Raw(String),
+}
+
+fuzz_target!(|input: Input| {
+    let hex: String = match input {
+        Input::Digits(digits) => digits
+            .into_iter()
+            .map(|digit| char::from(b"0123456789abcdefABCDEF"[digit as usize % 22]))
+            .collect(),
+        Input::Raw(raw) => raw,
+    };
+    let valid = hex.len() == 2 * XOR_NAME_LEN && hex.chars().all(|c| c.is_ascii_hexdigit());
+
+    match hex.parse::<XorName>() {
+        Ok(name) => {
+            assert!(valid);
+            assert_eq!(format!("{:x}", name), hex.to_lowercase());
+            assert_eq!(format!("{:X}", name).parse::<XorName>(), Ok(name));
+        }
+        Err(error) => {
+            assert!(!valid);
+            assert_eq!(error, Error::InvalidHex);
+        }
+    }
+});
@@ -0,0 +1,151 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::XorName;
+use core::{borrow::Borrow, iter::FromIterator};
+
+/// Returns the XOR of all `names`, or [`XorName::ZERO`] if there are none.
+///
+/// The result doesn't depend on the order of the names, and a name included twice cancels
+/// out, so it serves as a cheap checksum of a set of names. See [`XorAccumulator`] to update it
+/// incrementally.
+///
+/// ```
+/// use xor_name::{xor_all, xor_name};
+///
+/// let names = [xor_name!(0b1100), xor_name!(0b1010)];
+/// assert_eq!(xor_all(&names), xor_name!(0b0110));
+/// assert_eq!(xor_all(names.iter().rev()), xor_name!(0b0110));
+/// ```
+pub fn xor_all<I>(names: I) -> XorName
+where
+    I: IntoIterator,
+    I::Item: Borrow<XorName>,
+{
+    names.into_iter().collect::<XorAccumulator>().value()
+}
+
+/// The running XOR of a set of names, as computed by [`xor_all`].
+///
+/// Since XOR is its own inverse, [`XorAccumulator::remove`] is the same operation as
+/// [`XorAccumulator::add`]; both are provided to make the caller's intent clear. Adding a name
+/// that is already included removes it again, so the accumulator only identifies sets of
+/// distinct names.
+///
+/// ```
+/// use xor_name::{xor_name, XorAccumulator};
+///
+/// let mut checksum: XorAccumulator = [xor_name!(1), xor_name!(2)].iter().collect();
+/// checksum.add(&xor_name!(4));
+/// checksum.remove(&xor_name!(2));
+/// assert_eq!(checksum.value(), xor_name!(5));
+/// assert_eq!(checksum.len(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct XorAccumulator {
+    value: XorName,
+    len: usize,
+}
+
+impl XorAccumulator {
+    /// Creates an accumulator for the empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `name` to the set.
+    pub fn add(&mut self, name: &XorName) {
+        self.toggle(name);
+        self.len += 1;
+    }
+
+    /// Removes `name` from the set. The result is only meaningful if it was added before.
+    pub fn remove(&mut self, name: &XorName) {
+        self.toggle(name);
+        self.len = self.len.saturating_sub(1);
+    }
+
+    /// Returns the XOR of the names in the set.
+    pub fn value(&self) -> XorName {
+        self.value
+    }
+
+    /// Returns the number of names added minus the number removed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no names are left in the set.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn toggle(&mut self, name: &XorName) {
+        for (lhs, rhs) in self.value.0.iter_mut().zip(&name.0) {
+            *lhs ^= rhs;
+        }
+    }
+}
+
+impl<T: Borrow<XorName>> Extend<T> for XorAccumulator {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, names: I) {
+        names.into_iter().for_each(|name| self.add(name.borrow()));
+    }
+}
+
+impl<T: Borrow<XorName>> FromIterator<T> for XorAccumulator {
+    fn from_iter<I: IntoIterator<Item = T>>(names: I) -> Self {
+        let mut accumulator = Self::new();
+        accumulator.extend(names);
+        accumulator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XOR_NAME_LEN;
+    use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+
+    #[test]
+    fn xor_all_matches_bytewise_xor() {
+        let mut rng = SmallRng::from_entropy();
+        let mut names: Vec<XorName> = (0..100).map(|_| rng.gen()).collect();
+
+        let mut expected = [0; XOR_NAME_LEN];
+        for name in &names {
+            expected.iter_mut().zip(&name.0).for_each(|(e, b)| *e ^= b);
+        }
+        assert_eq!(xor_all(&names), XorName(expected));
+        names.shuffle(&mut rng);
+        assert_eq!(xor_all(names.iter().copied()), XorName(expected));
+
+        assert_eq!(xor_all(&names[..0]), XorName::ZERO);
+        assert_eq!(xor_all(&names[..1]), names[0]);
+    }
+
+    #[test]
+    fn accumulator() {
+        let mut rng = SmallRng::from_entropy();
+        let names: Vec<XorName> = (0..10).map(|_| rng.gen()).collect();
+
+        let mut accumulator = XorAccumulator::new();
+        assert!(accumulator.is_empty());
+        accumulator.extend(&names);
+        assert_eq!(accumulator.value(), xor_all(&names));
+        assert_eq!(accumulator.len(), names.len());
+
+        for name in &names[5..] {
+            accumulator.remove(name);
+        }
+        assert_eq!(accumulator, names[..5].iter().collect());
+        names[..5].iter().for_each(|name| accumulator.remove(name));
+        assert_eq!(accumulator, XorAccumulator::new());
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, Prefix, XorName};
+
+/// How a set of names is distributed over disjoint prefixes, compared to a uniform distribution.
+///
+/// For uniformly random names, the number of names matching a prefix is expected to be
+/// proportional to the size of the prefix's part of the name space, so a prefix of `n` bits is
+/// expected to get twice as many names as one of `n + 1` bits. The statistics here measure how
+/// far the actual counts are from that, to detect biased name generation or unbalanced sections.
+///
+/// ```
+/// use rand::{rngs::SmallRng, SeedableRng};
+/// use xor_name::{NameDistribution, XorName};
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let names: Vec<XorName> = (0..1000).map(|_| XorName::random(&mut rng)).collect();
+/// let distribution = NameDistribution::at_depth(4, &names);
+/// assert_eq!(distribution.counts().len(), 16);
+/// assert_eq!(distribution.total(), 1000);
+/// // For 15 degrees of freedom, uniform data exceeds 37.7 only once in a thousand runs.
+/// assert!(distribution.chi_square() < 50.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NameDistribution {
+    counts: Vec<(Prefix, usize)>,
+    unmatched: usize,
+    // The share of the name space covered by the prefixes.
+    coverage: f64,
+}
+
+impl NameDistribution {
+    /// The largest depth supported by [`NameDistribution::at_depth`].
+    pub const MAX_DEPTH: usize = 16;
+
+    /// Counts the `names` matching each of the given `prefixes`. Fails with
+    /// [`Error::OverlappingPrefixes`] if any two of the prefixes overlap.
+    ///
+    /// Names matching none of them are counted separately, see [`NameDistribution::unmatched`].
+    pub fn over<'a, I>(prefixes: &[Prefix], names: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a XorName>,
+    {
+        let mut counts: Vec<(Prefix, usize)> = prefixes.iter().map(|prefix| (*prefix, 0)).collect();
+        counts.sort_unstable_by_key(|(prefix, _)| prefix.lower_bound());
+        // A prefix containing another one comes right before it or one of its extensions.
+        if counts
+            .windows(2)
+            .any(|pair| pair[0].0.is_compatible(&pair[1].0))
+        {
+            return Err(Error::OverlappingPrefixes);
+        }
+        let mut unmatched = 0;
+
+        for name in names {
+            // The last prefix starting at or before `name` is the only one that can match it.
+            let i = counts.partition_point(|(prefix, _)| prefix.lower_bound() <= *name);
+            match i.checked_sub(1).map(|i| &mut counts[i]) {
+                Some((prefix, count)) if prefix.matches(name) => *count += 1,
+                _ => unmatched += 1,
+            }
+        }
+
+        Ok(Self::new(counts, unmatched))
+    }
+
+    /// Counts the `names` matching each of the `2^depth` prefixes of length `depth`. The depth is
+    /// capped at [`NameDistribution::MAX_DEPTH`].
+    pub fn at_depth<'a, I>(depth: usize, names: I) -> Self
+    where
+        I: IntoIterator<Item = &'a XorName>,
+    {
+        let depth = depth.min(Self::MAX_DEPTH);
+        let mut counts: Vec<(Prefix, usize)> = (0..1 << depth)
+            .filter_map(|index| Prefix::from_index(depth, index))
+            .map(|prefix| (prefix, 0))
+            .collect();
+        for name in names {
+            counts[name.bit_slice(0..depth) as usize].1 += 1;
+        }
+
+        Self::new(counts, 0)
+    }
+
+    fn new(counts: Vec<(Prefix, usize)>, unmatched: usize) -> Self {
+        let coverage = counts
+            .iter()
+            .map(|(prefix, _)| 0.5f64.powi(prefix.bit_count() as i32))
+            .sum();
+        Self {
+            counts,
+            unmatched,
+            coverage,
+        }
+    }
+
+    /// Returns each prefix with the number of names matching it, in name order.
+    pub fn counts(&self) -> &[(Prefix, usize)] {
+        &self.counts
+    }
+
+    /// Returns the number of names that matched one of the prefixes.
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Returns the number of names that matched none of the prefixes.
+    pub fn unmatched(&self) -> usize {
+        self.unmatched
+    }
+
+    /// Returns the number of names expected to match `prefix` if `total` names were uniformly
+    /// distributed over the prefixes.
+    fn expected(&self, prefix: &Prefix, total: usize) -> f64 {
+        total as f64 * 0.5f64.powi(prefix.bit_count() as i32) / self.coverage
+    }
+
+    /// Returns the ratios of each prefix's count to its expected count, in name order.
+    ///
+    /// A ratio of `1.0` is a perfect match, and e.g. `2.0` means twice as many names as
+    /// expected. Returns nothing if there are no names.
+    pub fn ratios(&self) -> impl Iterator<Item = f64> + '_ {
+        let total = self.total();
+        self.counts
+            .iter()
+            .filter(move |_| total > 0)
+            .map(move |(prefix, count)| *count as f64 / self.expected(prefix, total))
+    }
+
+    /// Returns the largest ratio of a prefix's count to its expected count, or `None` if there
+    /// are no names.
+    pub fn max_ratio(&self) -> Option<f64> {
+        self.ratios().reduce(f64::max)
+    }
+
+    /// Returns the smallest ratio of a prefix's count to its expected count, or `None` if there
+    /// are no names.
+    pub fn min_ratio(&self) -> Option<f64> {
+        self.ratios().reduce(f64::min)
+    }
+
+    /// Returns Pearson's chi-square statistic for the hypothesis that the names are uniformly
+    /// distributed, i.e. the sum of `(count - expected)^2 / expected` over all prefixes.
+    ///
+    /// Compare it against the chi-square distribution with
+    /// [`NameDistribution::degrees_of_freedom`] degrees of freedom: for uniform names it is about
+    /// equal to them, and values far beyond indicate bias.
+    pub fn chi_square(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.counts
+            .iter()
+            .map(|(prefix, count)| {
+                let expected = self.expected(prefix, total);
+                (*count as f64 - expected).powi(2) / expected
+            })
+            .sum()
+    }
+
+    /// Returns the degrees of freedom of the chi-square test: one less than the number of
+    /// prefixes.
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.counts.len().saturating_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XOR_NAME_LEN;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn at_depth() {
+        let mut rng = SmallRng::from_entropy();
+        let names: Vec<XorName> = (0..4000).map(|_| rng.gen()).collect();
+        let distribution = NameDistribution::at_depth(3, &names);
+        assert_eq!(distribution.counts().len(), 8);
+        assert_eq!(distribution.total(), 4000);
+        assert_eq!(distribution.unmatched(), 0);
+        assert_eq!(distribution.degrees_of_freedom(), 7);
+        for (prefix, count) in distribution.counts() {
+            assert_eq!(prefix.bit_count(), 3);
+            assert_eq!(
+                *count,
+                names.iter().filter(|name| prefix.matches(name)).count()
+            );
+        }
+        // Exceeded with probability below 1e-6 for uniform names.
+        assert!(distribution.chi_square() < 40.0);
+        assert!(distribution.max_ratio().unwrap() < 1.5);
+        assert!(distribution.min_ratio().unwrap() > 0.5);
+
+        assert_eq!(NameDistribution::at_depth(0, &names).counts().len(), 1);
+        assert_eq!(NameDistribution::at_depth(0, &names).chi_square(), 0.0);
+        assert_eq!(
+            NameDistribution::at_depth(usize::MAX, &names[..0])
+                .counts()
+                .len(),
+            1 << NameDistribution::MAX_DEPTH
+        );
+    }
+
+    #[test]
+    fn biased() {
+        let name = |byte: u8| XorName([byte; XOR_NAME_LEN]);
+        // Three quarters of the names start with `0`.
+        let names = [name(0x00), name(0x40), name(0x20), name(0x80)];
+        let distribution = NameDistribution::at_depth(1, &names);
+        assert_eq!(distribution.max_ratio(), Some(1.5));
+        assert_eq!(distribution.min_ratio(), Some(0.5));
+        // (3 - 2)^2 / 2 + (1 - 2)^2 / 2
+        assert_eq!(distribution.chi_square(), 1.0);
+
+        let empty = NameDistribution::at_depth(1, &names[..0]);
+        assert_eq!(empty.max_ratio(), None);
+        assert_eq!(empty.chi_square(), 0.0);
+    }
+
+    #[test]
+    fn over_prefixes() {
+        let parse = |bits: &str| bits.parse::<Prefix>().unwrap();
+        let name = |byte: u8| XorName([byte; XOR_NAME_LEN]);
+        // `0` is twice the size of `10`, and `11` is not covered.
+        let prefixes = [parse("10"), parse("0")];
+        let names = [name(0x00), name(0x40), name(0x80), name(0xC0), name(0xFF)];
+        let distribution = NameDistribution::over(&prefixes, &names).unwrap();
+
+        assert_eq!(distribution.counts(), [(parse("0"), 2), (parse("10"), 1)]);
+        assert_eq!(distribution.total(), 3);
+        assert_eq!(distribution.unmatched(), 2);
+        // Exactly as expected for uniform names over the covered three quarters.
+        assert_eq!(distribution.chi_square(), 0.0);
+        assert_eq!(distribution.max_ratio(), Some(1.0));
+    }
+
+    #[test]
+    fn overlapping_prefixes() {
+        let parse = |bits: &str| bits.parse::<Prefix>().unwrap();
+        for prefixes in [
+            [parse("0"), parse("1"), parse("0")],
+            [parse("01"), parse("1"), parse("0")],
+            [parse("0"), parse("1"), parse("011")],
+            [parse(""), parse("10"), parse("11")],
+        ] {
+            assert_eq!(
+                NameDistribution::over(&prefixes, &[]).unwrap_err(),
+                Error::OverlappingPrefixes
+            );
+        }
+        assert!(NameDistribution::over(&[parse("00"), parse("011"), parse("1")], &[]).is_ok());
+        assert!(NameDistribution::over(&[], &[]).is_ok());
+    }
+}
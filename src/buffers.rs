@@ -0,0 +1,110 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, XorName, XOR_NAME_LEN};
+use bytes::{BufMut, Bytes, BytesMut};
+use core::convert::TryFrom;
+
+/// Converts a buffer of exactly `XOR_NAME_LEN` bytes into a `XorName`. Returns
+/// `Error::InvalidLength` if its length is wrong.
+impl TryFrom<Bytes> for XorName {
+    type Error = Error;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        XorName::try_from(&bytes[..])
+    }
+}
+
+impl From<XorName> for Bytes {
+    fn from(name: XorName) -> Self {
+        Bytes::copy_from_slice(&name.0)
+    }
+}
+
+/// Splits a buffer of back-to-back names into `XorName`s. Returns `Error::InvalidLength`, with
+/// the length of the whole names in the buffer as `expected`, if the buffer length is not a
+/// multiple of `XOR_NAME_LEN`.
+pub fn names_from_bytes(bytes: &[u8]) -> Result<Vec<XorName>, Error> {
+    if bytes.len() % XOR_NAME_LEN != 0 {
+        return Err(Error::InvalidLength {
+            expected: bytes.len() - bytes.len() % XOR_NAME_LEN,
+            actual: bytes.len(),
+        });
+    }
+    Ok(bytes
+        .chunks_exact(XOR_NAME_LEN)
+        .map(|chunk| {
+            let mut name = XorName::default();
+            name.0.copy_from_slice(chunk);
+            name
+        })
+        .collect())
+}
+
+/// Writes the names back-to-back into a single buffer, the inverse of `names_from_bytes`.
+pub fn names_to_bytes(names: &[XorName]) -> Bytes {
+    let mut buf = BytesMut::with_capacity(names.len() * XOR_NAME_LEN);
+    for name in names {
+        buf.put_slice(&name.0);
+    }
+    buf.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn bytes_roundtrip() {
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let bytes = Bytes::from(name);
+        assert_eq!(&bytes[..], &name[..]);
+        assert_eq!(XorName::try_from(bytes), Ok(name));
+    }
+
+    #[test]
+    fn bytes_wrong_length() {
+        let short = Bytes::from_static(&[1; XOR_NAME_LEN - 1]);
+        assert_eq!(
+            XorName::try_from(short),
+            Err(Error::InvalidLength {
+                expected: XOR_NAME_LEN,
+                actual: XOR_NAME_LEN - 1
+            })
+        );
+        let long = Bytes::from_static(&[1; XOR_NAME_LEN + 1]);
+        assert_eq!(
+            XorName::try_from(long),
+            Err(Error::InvalidLength {
+                expected: XOR_NAME_LEN,
+                actual: XOR_NAME_LEN + 1
+            })
+        );
+    }
+
+    #[test]
+    fn names_roundtrip() {
+        let mut rng = SmallRng::from_entropy();
+        let names: Vec<XorName> = (0..5).map(|_| rng.gen()).collect();
+        let bytes = names_to_bytes(&names);
+        assert_eq!(bytes.len(), 5 * XOR_NAME_LEN);
+        assert_eq!(names_from_bytes(&bytes), Ok(names));
+
+        assert_eq!(names_from_bytes(&[]), Ok(vec![]));
+        assert_eq!(
+            names_from_bytes(&bytes[1..]),
+            Err(Error::InvalidLength {
+                expected: 4 * XOR_NAME_LEN,
+                actual: 5 * XOR_NAME_LEN - 1
+            })
+        );
+    }
+}
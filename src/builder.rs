@@ -0,0 +1,146 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Prefix, XorName, XOR_NAME_LEN};
+
+/// Builds a name satisfying several constraints at once.
+///
+/// Each method fixes some bits of the name, overriding whatever earlier calls set for the same
+/// bits. [`XorNameBuilder::random_suffix`] then randomizes all the bits that haven't been fixed,
+/// not only those after the prefix, so it can be called at any point. Bits that are neither
+/// fixed nor randomized are `0`.
+///
+/// ```
+/// use rand::{rngs::SmallRng, SeedableRng};
+/// use xor_name::{prefix, XorNameBuilder};
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let name = XorNameBuilder::new()
+///     .with_prefix(&prefix!("0110"))
+///     .with_bit(255, true)
+///     .random_suffix(&mut rng)
+///     .build();
+/// assert!(prefix!("0110").matches(&name));
+/// assert!(name.bit(255));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XorNameBuilder {
+    name: XorName,
+    // The bits of `name` that have been fixed.
+    fixed: XorName,
+}
+
+impl XorNameBuilder {
+    /// Creates a builder with no bits fixed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fixes the leading bits of the name to those of `prefix`.
+    pub fn with_prefix(mut self, prefix: &Prefix) -> Self {
+        self.name = prefix.substituted_in(self.name);
+        self.fixed = Prefix::new(prefix.bit_count(), !XorName::ZERO).substituted_in(self.fixed);
+        self
+    }
+
+    /// Fixes the `i`-th bit of the name to `bit`.
+    pub fn with_bit(mut self, i: u8, bit: bool) -> Self {
+        self.name = self.name.with_bit(i, bit);
+        self.fixed = self.fixed.with_bit(i, true);
+        self
+    }
+
+    /// Fixes the `i`-th byte of the name to `byte`.
+    ///
+    /// If `i` is not less than the 32 bytes of a name, the builder is returned unchanged.
+    pub fn with_byte(mut self, i: usize, byte: u8) -> Self {
+        if i < XOR_NAME_LEN {
+            self.name.0[i] = byte;
+            self.fixed.0[i] = u8::MAX;
+        }
+        self
+    }
+
+    /// Sets all bits that haven't been fixed yet to random values. They remain unfixed, so
+    /// later calls can still override them.
+    pub fn random_suffix<R: rand::Rng>(mut self, rng: &mut R) -> Self {
+        let random = XorName::random(rng);
+        for ((byte, fixed), random) in self.name.0.iter_mut().zip(&self.fixed.0).zip(&random.0) {
+            *byte = (*byte & fixed) | (random & !fixed);
+        }
+        self
+    }
+
+    /// Returns the name.
+    pub fn build(&self) -> XorName {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn constraints() {
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let prefix = Prefix::new(rng.gen_range(0..=256), rng.gen());
+            let (bit, byte) = (rng.gen::<u8>(), rng.gen_range(0..XOR_NAME_LEN));
+            let builder = XorNameBuilder::new()
+                .with_byte(byte, 0xA5)
+                .with_prefix(&prefix)
+                .with_bit(bit, true);
+            let name = builder.random_suffix(&mut rng).build();
+
+            assert!(name.bit(bit));
+            // Apart from the bit set last, the prefix and the byte still apply.
+            assert!(prefix.matches(&name.with_bit(bit, prefix.name().bit(bit))));
+            if 8 * byte >= prefix.bit_count() {
+                let expected = XorName([0xA5; XOR_NAME_LEN]);
+                assert_eq!(name.with_bit(bit, expected.bit(bit))[byte], 0xA5);
+            }
+
+            // Randomizing again only changes the unfixed bits.
+            let other = builder.random_suffix(&mut rng).build();
+            assert!((0..XOR_NAME_LEN).all(|i| (name[i] ^ other[i]) & builder.fixed[i] == 0));
+        }
+    }
+
+    #[test]
+    fn unfixed_bits() {
+        let mut rng = SmallRng::from_entropy();
+        assert_eq!(XorNameBuilder::new().build(), XorName::ZERO);
+        assert_eq!(
+            XorNameBuilder::new().with_byte(XOR_NAME_LEN, 1).build(),
+            XorName::ZERO
+        );
+
+        // With a fixed first bit, random names differ only in the remaining bits.
+        let names: Vec<XorName> = (0..64)
+            .map(|_| {
+                XorNameBuilder::new()
+                    .with_bit(0, true)
+                    .random_suffix(&mut rng)
+                    .build()
+            })
+            .collect();
+        assert!(names.iter().all(|name| name.bit(0)));
+        assert!(names.iter().any(|name| name.bit(1)));
+        assert!(names.iter().any(|name| !name.bit(1)));
+
+        // Fixing a bit again overrides it.
+        let name = XorNameBuilder::new()
+            .with_prefix(&"11".parse().unwrap())
+            .with_bit(1, false)
+            .build();
+        assert_eq!(name, xor_name!(0b1000_0000));
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, Prefix, XorName};
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+/// A `Prefix` of at most 64 bits, stored in 9 bytes instead of 34.
+///
+/// Section prefixes are usually short, so maps and sets holding many of them can use this type
+/// for their keys, and convert to `Prefix` to work with them. Converting from a `Prefix` fails if
+/// it is longer than [`CompactPrefix::MAX_BIT_COUNT`] bits.
+///
+/// Equality, ordering and formatting are the same as for the corresponding `Prefix`.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use xor_name::{CompactPrefix, Prefix};
+///
+/// let prefix: Prefix = "0110".parse().unwrap();
+/// let compact = CompactPrefix::try_from(prefix).unwrap();
+/// assert_eq!(Prefix::from(compact), prefix);
+/// assert_eq!(compact.to_string(), "0110");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CompactPrefix {
+    bit_count: u8,
+    // The first 8 bytes of the prefix's name, with all insignificant bits cleared.
+    bits: [u8; 8],
+}
+
+impl CompactPrefix {
+    /// The maximum number of bits of a `CompactPrefix`.
+    pub const MAX_BIT_COUNT: usize = 64;
+
+    /// Returns the number of bits in the prefix.
+    pub fn bit_count(&self) -> usize {
+        self.bit_count as usize
+    }
+}
+
+impl TryFrom<Prefix> for CompactPrefix {
+    type Error = Error;
+
+    /// Fails with [`Error::LossyConversion`] if the prefix has more than 64 bits.
+    fn try_from(prefix: Prefix) -> Result<Self, Self::Error> {
+        if prefix.bit_count() > Self::MAX_BIT_COUNT {
+            return Err(Error::LossyConversion);
+        }
+        let mut bits = [0; 8];
+        bits.copy_from_slice(&prefix.lower_bound()[..8]);
+        Ok(Self {
+            bit_count: prefix.bit_count() as u8,
+            bits,
+        })
+    }
+}
+
+impl From<CompactPrefix> for Prefix {
+    fn from(compact: CompactPrefix) -> Self {
+        let mut name = XorName::ZERO;
+        name[..8].copy_from_slice(&compact.bits);
+        Prefix::new(compact.bit_count(), name)
+    }
+}
+
+impl PartialOrd for CompactPrefix {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompactPrefix {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Prefix::from(*self).cmp(&Prefix::from(*other))
+    }
+}
+
+impl Debug for CompactPrefix {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "CompactPrefix({})", Prefix::from(*self))
+    }
+}
+
+impl Display for CompactPrefix {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(&Prefix::from(*self), formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::size_of;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn size() {
+        assert_eq!(size_of::<CompactPrefix>(), 9);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut rng = SmallRng::from_entropy();
+        for bit_count in 0..=CompactPrefix::MAX_BIT_COUNT {
+            let prefix = Prefix::new(bit_count, rng.gen());
+            let compact = CompactPrefix::try_from(prefix).unwrap();
+            assert_eq!(compact.bit_count(), bit_count);
+            assert_eq!(Prefix::from(compact), prefix);
+            assert_eq!(Prefix::from(compact).name(), prefix.name());
+            assert_eq!(compact.to_string(), prefix.to_string());
+        }
+
+        let long = Prefix::new(65, rng.gen());
+        assert_eq!(CompactPrefix::try_from(long), Err(Error::LossyConversion));
+    }
+
+    #[test]
+    fn order_matches_prefix() {
+        let mut rng = SmallRng::from_entropy();
+        let prefixes: Vec<Prefix> = (0..200)
+            .map(|_| Prefix::new(rng.gen_range(0..8), rng.gen()))
+            .collect();
+        for lhs in &prefixes {
+            for rhs in &prefixes {
+                let compact_lhs = CompactPrefix::try_from(*lhs).unwrap();
+                let compact_rhs = CompactPrefix::try_from(*rhs).unwrap();
+                assert_eq!(compact_lhs.cmp(&compact_rhs), lhs.cmp(rhs));
+                assert_eq!(compact_lhs == compact_rhs, lhs == rhs);
+            }
+        }
+    }
+}
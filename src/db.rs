@@ -0,0 +1,573 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+// Database column support, enabled with the `sqlx` and `diesel` features.
+//
+// `XorName` is stored as its 32 raw bytes in a binary column (`BYTEA`/`BLOB`). `Prefix` is stored
+// in the same column type using its compact encoding: the bit count as two big-endian bytes,
+// followed by only the significant bytes of the name.
+//
+// Both types are usable in `sqlx` queries for any database with binary columns. For `diesel`,
+// they are `Binary` expressions and can be loaded from `Binary` columns on any backend. Writing a
+// `Prefix` builds its compact encoding on the fly, which each backend accepts differently, so that
+// is enabled per backend by the `diesel-postgres`, `diesel-mysql` and `diesel-sqlite` features.
+
+#[cfg(feature = "sqlx")]
+mod sqlx_impls {
+    use crate::{Prefix, XorName, XOR_NAME_LEN};
+    use core::convert::TryFrom;
+    use sqlx::{
+        encode::IsNull,
+        error::BoxDynError,
+        {Database, Decode, Encode, Type},
+    };
+
+    impl<DB: Database> Type<DB> for XorName
+    where
+        Vec<u8>: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <Vec<u8> as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <Vec<u8> as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for XorName
+    where
+        Vec<u8>: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            self.0.to_vec().encode_by_ref(buf)
+        }
+
+        fn size_hint(&self) -> usize {
+            XOR_NAME_LEN
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for XorName
+    where
+        &'r [u8]: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            let bytes = <&[u8] as Decode<DB>>::decode(value)?;
+            Ok(XorName::try_from(bytes)?)
+        }
+    }
+
+    impl<DB: Database> Type<DB> for Prefix
+    where
+        Vec<u8>: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <Vec<u8> as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <Vec<u8> as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for Prefix
+    where
+        Vec<u8>: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            self.to_compact_bytes().encode_by_ref(buf)
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for Prefix
+    where
+        &'r [u8]: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            let bytes = <&[u8] as Decode<DB>>::decode(value)?;
+            Prefix::from_compact_bytes(bytes).ok_or_else(|| "invalid Prefix encoding".into())
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+mod diesel_impls {
+    use crate::{Prefix, XorName};
+    use core::convert::TryFrom;
+    #[cfg(feature = "diesel-mysql")]
+    use diesel::mysql::Mysql;
+    #[cfg(feature = "diesel-postgres")]
+    use diesel::pg::Pg;
+    #[cfg(any(
+        feature = "diesel-postgres",
+        feature = "diesel-mysql",
+        feature = "diesel-sqlite"
+    ))]
+    use diesel::serialize::IsNull;
+    #[cfg(feature = "diesel-sqlite")]
+    use diesel::sqlite::Sqlite;
+    use diesel::{
+        backend::Backend,
+        deserialize::{self, FromSql},
+        serialize::{self, Output, ToSql},
+        sql_types::Binary,
+    };
+    #[cfg(any(feature = "diesel-postgres", feature = "diesel-mysql"))]
+    use std::io::Write;
+
+    impl<DB: Backend> ToSql<Binary, DB> for XorName
+    where
+        [u8]: ToSql<Binary, DB>,
+    {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+            <[u8] as ToSql<Binary, DB>>::to_sql(&self.0[..], out)
+        }
+    }
+
+    impl<DB: Backend> FromSql<Binary, DB> for XorName
+    where
+        Vec<u8>: FromSql<Binary, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+            let bytes = <Vec<u8> as FromSql<Binary, DB>>::from_sql(bytes)?;
+            Ok(XorName::try_from(&bytes[..])?)
+        }
+    }
+
+    // The compact encoding is built on the fly, so it is copied into the bind buffer where the
+    // backend has one and handed over as an owned value on SQLite, which otherwise borrows.
+    #[cfg(feature = "diesel-postgres")]
+    impl ToSql<Binary, Pg> for Prefix {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+            out.write_all(&self.to_compact_bytes())?;
+            Ok(IsNull::No)
+        }
+    }
+
+    #[cfg(feature = "diesel-mysql")]
+    impl ToSql<Binary, Mysql> for Prefix {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> serialize::Result {
+            out.write_all(&self.to_compact_bytes())?;
+            Ok(IsNull::No)
+        }
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    impl ToSql<Binary, Sqlite> for Prefix {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+            out.set_value(self.to_compact_bytes());
+            Ok(IsNull::No)
+        }
+    }
+
+    impl<DB: Backend> FromSql<Binary, DB> for Prefix
+    where
+        Vec<u8>: FromSql<Binary, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+            let bytes = <Vec<u8> as FromSql<Binary, DB>>::from_sql(bytes)?;
+            Prefix::from_compact_bytes(&bytes).ok_or_else(|| "invalid Prefix encoding".into())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlx-sqlite"))]
+mod sqlx_tests {
+    use crate::{Prefix, XorName};
+    use core::future::Future;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use sqlx::{Connection, SqliteConnection};
+    use std::{
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread},
+    };
+
+    // The SQLite driver runs on its own thread, so a minimal executor is enough to drive it.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct Unpark(Thread);
+
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn sqlite_roundtrip() {
+        let mut rng = SmallRng::from_entropy();
+        let rows: Vec<(XorName, Prefix)> = (0..=256)
+            .step_by(17)
+            .map(|bit_count| (rng.gen(), Prefix::new(bit_count, rng.gen())))
+            .collect();
+
+        block_on(async {
+            let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            let _ = sqlx::query("CREATE TABLE sections (name BLOB NOT NULL, prefix BLOB NOT NULL)")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+            for (name, prefix) in &rows {
+                let _ = sqlx::query("INSERT INTO sections VALUES (?, ?)")
+                    .bind(name)
+                    .bind(prefix)
+                    .execute(&mut conn)
+                    .await
+                    .unwrap();
+            }
+
+            let loaded: Vec<(XorName, Prefix)> =
+                sqlx::query_as("SELECT name, prefix FROM sections ORDER BY rowid")
+                    .fetch_all(&mut conn)
+                    .await
+                    .unwrap();
+            assert_eq!(loaded, rows);
+
+            let (name, prefix) = rows[3];
+            let found: XorName = sqlx::query_scalar("SELECT name FROM sections WHERE prefix = ?")
+                .bind(prefix)
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(found, name);
+
+            // A truncated name, and a prefix whose bit count needs a byte that is missing.
+            assert!(sqlx::query_scalar::<_, XorName>("SELECT x'0102'")
+                .fetch_one(&mut conn)
+                .await
+                .is_err());
+            assert!(sqlx::query_scalar::<_, Prefix>("SELECT x'0003'")
+                .fetch_one(&mut conn)
+                .await
+                .is_err());
+        });
+    }
+}
+
+#[cfg(all(test, feature = "diesel-sqlite"))]
+mod diesel_tests {
+    use crate::{Prefix, XorName};
+    use diesel::{connection::SimpleConnection, prelude::*, sqlite::SqliteConnection};
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    diesel::table! {
+        sections (id) {
+            id -> Integer,
+            name -> Binary,
+            prefix -> Binary,
+            parent -> Nullable<Binary>,
+        }
+    }
+
+    #[derive(Debug, PartialEq, Insertable, Queryable)]
+    #[diesel(table_name = sections)]
+    struct Section {
+        id: i32,
+        name: XorName,
+        prefix: Prefix,
+        parent: Option<Prefix>,
+    }
+
+    #[test]
+    fn sqlite_roundtrip() {
+        let mut rng = SmallRng::from_entropy();
+        let rows: Vec<Section> = (0..=256)
+            .step_by(17)
+            .map(|bit_count| {
+                let prefix = Prefix::new(bit_count, rng.gen());
+                Section {
+                    id: bit_count as i32,
+                    name: prefix.substituted_in(rng.gen()),
+                    prefix,
+                    parent: (bit_count > 0).then(|| prefix.popped()),
+                }
+            })
+            .collect();
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.batch_execute(
+            "CREATE TABLE sections (
+                id INTEGER PRIMARY KEY NOT NULL,
+                name BLOB NOT NULL,
+                prefix BLOB NOT NULL,
+                parent BLOB
+            )",
+        )
+        .unwrap();
+        for row in &rows {
+            let _ = diesel::insert_into(sections::table)
+                .values(row)
+                .execute(&mut conn)
+                .unwrap();
+        }
+
+        let loaded: Vec<Section> = sections::table.order(sections::id).load(&mut conn).unwrap();
+        assert_eq!(loaded, rows);
+
+        let found: i32 = sections::table
+            .filter(sections::prefix.eq(rows[3].prefix))
+            .filter(sections::name.eq(&rows[3].name))
+            .select(sections::id)
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(found, rows[3].id);
+
+        // A prefix whose bit count needs a byte that is missing.
+        conn.batch_execute("INSERT INTO sections VALUES (1000, zeroblob(32), x'0003', NULL)")
+            .unwrap();
+        assert!(sections::table
+            .filter(sections::id.eq(1000))
+            .first::<Section>(&mut conn)
+            .is_err());
+    }
+}
+
+// The Postgres and MySQL encodings, checked against raw values without a database server.
+#[cfg(all(test, any(feature = "diesel-postgres", feature = "diesel-mysql")))]
+mod diesel_raw_tests {
+    use super::tests::{compact, samples};
+    use crate::{Prefix, XorName};
+    use diesel::{
+        backend::Backend,
+        deserialize::FromSql,
+        query_builder::{bind_collector::RawBytesBindCollector, BindCollector},
+        serialize::ToSql,
+        sql_types::{Binary, HasSqlType},
+    };
+
+    // Encodes `value` as a bind parameter, as a query would.
+    fn encode<DB, T>(value: &T, lookup: &mut DB::MetadataLookup) -> Vec<u8>
+    where
+        for<'c> DB: Backend<BindCollector<'c> = RawBytesBindCollector<DB>> + HasSqlType<Binary>,
+        T: ToSql<Binary, DB>,
+    {
+        let mut collector = RawBytesBindCollector::<DB>::new();
+        collector
+            .push_bound_value::<Binary, T>(value, lookup)
+            .unwrap();
+        collector.binds.pop().unwrap().unwrap()
+    }
+
+    #[cfg(feature = "diesel-postgres")]
+    #[test]
+    fn postgres() {
+        use core::num::NonZeroU32;
+        use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+
+        // `Binary` has a fixed type OID, so nothing needs to be looked up.
+        struct NoLookup;
+
+        impl PgMetadataLookup for NoLookup {
+            fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+                unreachable!()
+            }
+        }
+
+        const BYTEA: NonZeroU32 = match NonZeroU32::new(17) {
+            Some(oid) => oid,
+            None => panic!(),
+        };
+        fn value(bytes: &[u8]) -> PgValue<'_> {
+            PgValue::new(bytes, &BYTEA)
+        }
+
+        for (name, prefix) in samples() {
+            let bytes = encode::<Pg, _>(&name, &mut NoLookup);
+            assert_eq!(bytes, name.0);
+            assert_eq!(
+                <XorName as FromSql<Binary, Pg>>::from_sql(value(&bytes)).unwrap(),
+                name
+            );
+
+            let bytes = encode::<Pg, _>(&prefix, &mut NoLookup);
+            assert_eq!(bytes, compact(&prefix));
+            assert_eq!(
+                <Prefix as FromSql<Binary, Pg>>::from_sql(value(&bytes)).unwrap(),
+                prefix
+            );
+        }
+
+        assert!(<XorName as FromSql<Binary, Pg>>::from_sql(value(&[1, 2])).is_err());
+        assert!(<Prefix as FromSql<Binary, Pg>>::from_sql(value(&[0, 3])).is_err());
+    }
+
+    #[cfg(feature = "diesel-mysql")]
+    #[test]
+    fn mysql() {
+        use diesel::mysql::{Mysql, MysqlType, MysqlValue};
+
+        fn value(bytes: &[u8]) -> MysqlValue<'_> {
+            MysqlValue::new(bytes, MysqlType::Blob)
+        }
+
+        for (name, prefix) in samples() {
+            let bytes = encode::<Mysql, _>(&name, &mut ());
+            assert_eq!(bytes, name.0);
+            assert_eq!(
+                <XorName as FromSql<Binary, Mysql>>::from_sql(value(&bytes)).unwrap(),
+                name
+            );
+
+            let bytes = encode::<Mysql, _>(&prefix, &mut ());
+            assert_eq!(bytes, compact(&prefix));
+            assert_eq!(
+                <Prefix as FromSql<Binary, Mysql>>::from_sql(value(&bytes)).unwrap(),
+                prefix
+            );
+        }
+
+        assert!(<XorName as FromSql<Binary, Mysql>>::from_sql(value(&[1, 2])).is_err());
+        assert!(<Prefix as FromSql<Binary, Mysql>>::from_sql(value(&[0, 3])).is_err());
+    }
+}
+
+// sqlx can only decode values received from a server, so these check the encoding side.
+#[cfg(all(test, any(feature = "sqlx-postgres", feature = "sqlx-mysql")))]
+mod sqlx_raw_tests {
+    use super::tests::{compact, samples};
+    use sqlx::{Encode, Type};
+
+    #[cfg(feature = "sqlx-postgres")]
+    #[test]
+    fn postgres() {
+        use sqlx::postgres::{PgArgumentBuffer, Postgres};
+
+        for (name, prefix) in samples() {
+            let mut buf = PgArgumentBuffer::default();
+            let _ = Encode::<Postgres>::encode_by_ref(&name, &mut buf).unwrap();
+            assert_eq!(&buf[..], &name.0[..]);
+
+            let mut buf = PgArgumentBuffer::default();
+            let _ = Encode::<Postgres>::encode_by_ref(&prefix, &mut buf).unwrap();
+            assert_eq!(&buf[..], &compact(&prefix)[..]);
+        }
+        assert_eq!(
+            <crate::Prefix as Type<Postgres>>::type_info(),
+            <Vec<u8> as Type<Postgres>>::type_info()
+        );
+    }
+
+    #[cfg(feature = "sqlx-mysql")]
+    #[test]
+    fn mysql() {
+        use sqlx::mysql::MySql;
+
+        // Byte strings are sent with a length prefix, a single byte for short values.
+        for (name, prefix) in samples() {
+            let mut buf = Vec::new();
+            let _ = Encode::<MySql>::encode_by_ref(&name, &mut buf).unwrap();
+            assert_eq!(buf[0] as usize, crate::XOR_NAME_LEN);
+            assert_eq!(&buf[1..], &name.0[..]);
+
+            let mut buf = Vec::new();
+            let _ = Encode::<MySql>::encode_by_ref(&prefix, &mut buf).unwrap();
+            let expected = compact(&prefix);
+            assert_eq!(buf[0] as usize, expected.len());
+            assert_eq!(&buf[1..], &expected[..]);
+        }
+        assert_eq!(
+            <crate::Prefix as Type<MySql>>::type_info(),
+            <Vec<u8> as Type<MySql>>::type_info()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Prefix;
+    use core::str::FromStr;
+
+    // The compact encoding of `prefix`, spelled out byte by byte.
+    #[cfg(any(
+        feature = "diesel-postgres",
+        feature = "diesel-mysql",
+        feature = "sqlx-postgres",
+        feature = "sqlx-mysql"
+    ))]
+    pub(super) fn compact(prefix: &Prefix) -> Vec<u8> {
+        let mut bytes = vec![(prefix.bit_count() >> 8) as u8, prefix.bit_count() as u8];
+        bytes.extend_from_slice(&prefix.name().0[..prefix.bit_count().div_ceil(8)]);
+        bytes
+    }
+
+    // Names and prefixes whose lengths cover empty, partial and full bytes.
+    #[cfg(any(
+        feature = "diesel-postgres",
+        feature = "diesel-mysql",
+        feature = "sqlx-postgres",
+        feature = "sqlx-mysql"
+    ))]
+    pub(super) fn samples() -> Vec<(crate::XorName, Prefix)> {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_entropy();
+        [0, 1, 7, 8, 9, 100, 255, 256]
+            .iter()
+            .map(|bit_count| (rng.gen(), Prefix::new(*bit_count, rng.gen())))
+            .collect()
+    }
+
+    #[test]
+    fn prefix_compact_roundtrip() {
+        for bits in [
+            "",
+            "0",
+            "1",
+            "0110",
+            "10110011",
+            "101100111",
+            &"10".repeat(128),
+        ] {
+            let prefix = Prefix::from_str(bits).unwrap();
+            let bytes = prefix.to_compact_bytes();
+            assert_eq!(bytes.len(), 2 + bits.len().div_ceil(8));
+            assert_eq!(Prefix::from_compact_bytes(&bytes), Some(prefix));
+        }
+    }
+
+    #[test]
+    fn prefix_compact_rejects_malformed() {
+        assert_eq!(Prefix::from_compact_bytes(&[]), None);
+        assert_eq!(Prefix::from_compact_bytes(&[0]), None);
+        // bit count says one byte follows, but there are none
+        assert_eq!(Prefix::from_compact_bytes(&[0, 3]), None);
+        // too many bytes
+        assert_eq!(Prefix::from_compact_bytes(&[0, 3, 0xff, 0xff]), None);
+        // bit count above 256
+        let mut too_long = vec![1, 1];
+        too_long.extend_from_slice(&[0; 33]);
+        assert_eq!(Prefix::from_compact_bytes(&too_long), None);
+    }
+
+    #[test]
+    fn prefix_compact_clears_insignificant_bits() {
+        let prefix = Prefix::from_compact_bytes(&[0, 3, 0b1011_1111]).unwrap();
+        assert_eq!(prefix, Prefix::from_str("101").unwrap());
+        assert_eq!(prefix.name(), xor_name!(0b1010_0000));
+        assert_eq!(prefix.to_compact_bytes(), [0, 3, 0b1010_0000]);
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Prefix, XorName, XOR_NAME_LEN};
+use core::{
+    borrow::Borrow,
+    cmp::{Ordering, Reverse},
+    fmt,
+    iter::FusedIterator,
+};
+use std::collections::{BTreeSet, BinaryHeap};
+
+/// The XOR distance between two names.
+///
+/// Distances are ordered numerically, so they can be used as keys in sorted containers. For a
+/// fixed target, ordering names by their distance to it gives the same result as
+/// [`XorName::cmp_distance`].
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct XorDistance(pub [u8; XOR_NAME_LEN]);
+
+impl XorDistance {
+    /// Returns the number of leading zero bits, which is also the length of the common prefix
+    /// of the two names the distance was computed from.
+    pub fn leading_zeros(&self) -> usize {
+        self.0
+            .iter()
+            .position(|byte| *byte != 0)
+            .map_or(8 * XOR_NAME_LEN, |i| {
+                8 * i + self.0[i].leading_zeros() as usize
+            })
+    }
+}
+
+impl AsRef<[u8]> for XorDistance {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl fmt::Debug for XorDistance {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "XorDistance({:x})", XorName(self.0))
+    }
+}
+
+/// Orders names by their XOR distance from a target.
+///
+/// Use [`DistanceFrom::cmp`] with `sort_by`, or [`DistanceFrom::key`] to key a `BTreeMap` by
+/// distance; [`DistanceFrom::name`] turns such a key back into the name.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use xor_name::{xor_name, DistanceFrom};
+///
+/// let target = xor_name!(0b0100_0000);
+/// let by_distance = DistanceFrom(&target);
+///
+/// let mut peers = BTreeMap::new();
+/// for (name, addr) in [(xor_name!(0b1000_0000), "far"), (xor_name!(0b0110_0000), "near")] {
+///     let _ = peers.insert(by_distance.key(&name), addr);
+/// }
+/// let (closest, addr) = peers.iter().next().unwrap();
+/// assert_eq!(by_distance.name(closest), xor_name!(0b0110_0000));
+/// assert_eq!(*addr, "near");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DistanceFrom<'a>(pub &'a XorName);
+
+impl DistanceFrom<'_> {
+    /// Compares the distances of `lhs` and `rhs` from the target. Returns `Less` if `lhs` is
+    /// closer.
+    pub fn cmp(&self, lhs: &XorName, rhs: &XorName) -> Ordering {
+        self.0.cmp_distance(lhs, rhs)
+    }
+
+    /// Returns the distance of `name` from the target.
+    pub fn key(&self, name: &XorName) -> XorDistance {
+        self.0.distance(name)
+    }
+
+    /// Returns the name at the given distance from the target. This is the inverse of `key`.
+    pub fn name(&self, distance: &XorDistance) -> XorName {
+        self.0.distance(&XorName(distance.0)).into()
+    }
+}
+
+impl From<XorDistance> for XorName {
+    fn from(distance: XorDistance) -> Self {
+        XorName(distance.0)
+    }
+}
+
+/// Returns the `k` names in `names` closest to `target`, closest first.
+///
+/// Instead of scanning the whole set, this walks the binary tree of prefixes, visiting the half
+/// containing `target` first. All names in that half are closer than any name in the other one,
+/// so subtrees are visited in order of distance, and only the range queries for the subtrees
+/// on the way to the `k` closest names are performed.
+pub fn k_closest(names: &BTreeSet<XorName>, target: &XorName, k: usize) -> Vec<XorName> {
+    let mut closest = Vec::with_capacity(k.min(names.len()));
+    // Subtrees yet to be visited, the closest one last.
+    let mut pending = vec![Prefix::default()];
+
+    while let Some(prefix) = pending.pop() {
+        let wanted = k - closest.len();
+        if wanted == 0 {
+            break;
+        }
+
+        // If all of the subtree is needed, take it as a whole. Otherwise split it.
+        let start = closest.len();
+        closest.extend(names.range(prefix.range_inclusive()).take(wanted + 1));
+        if closest.len() - start <= wanted {
+            closest[start..].sort_unstable_by(|lhs, rhs| target.cmp_distance(lhs, rhs));
+        } else {
+            closest.truncate(start);
+            // More than one name, so `prefix` can't have the full 256 bits.
+            let near = prefix.pushed(target.bit(prefix.bit_count() as u8));
+            pending.push(near.sibling());
+            pending.push(near);
+        }
+    }
+
+    closest
+}
+
+/// Returns the `names` in increasing distance from `target`, computing the order lazily.
+///
+/// The names are collected into a binary heap in linear time, and each one taken costs
+/// `O(log n)`, so taking the `k` closest of `n` names costs `O(n + k log n)` instead of the
+/// `O(n log n)` of a full sort. Duplicates are kept, and names at equal distance (which can only
+/// be duplicates) come out together.
+///
+/// The heap holds all `n` names, so this needs `O(n)` memory. If at most `k` names will be
+/// taken, [`closest_first_bounded`] needs only `O(k)`.
+///
+/// ```
+/// use xor_name::{closest_first, xor_name};
+///
+/// let peers = [xor_name!(0b1000_0000), xor_name!(0b0110_0000), xor_name!(0b0100_0001)];
+/// let target = xor_name!(0b0100_0000);
+/// let closest: Vec<_> = closest_first(&peers, &target).take(2).collect();
+/// assert_eq!(closest, [xor_name!(0b0100_0001), xor_name!(0b0110_0000)]);
+/// ```
+pub fn closest_first<I>(names: I, target: &XorName) -> ClosestFirst
+where
+    I: IntoIterator,
+    I::Item: Borrow<XorName>,
+{
+    ClosestFirst {
+        target: *target,
+        heap: names
+            .into_iter()
+            .map(|name| Reverse(target.distance(name.borrow())))
+            .collect(),
+    }
+}
+
+/// Returns the `k` names closest to `target` in increasing distance, like [`closest_first`], but
+/// only ever keeping `k` names in memory.
+///
+/// The names are passed through a heap of the `k` closest seen so far, which costs
+/// `O(n log k)`. Duplicates are kept, and count towards `k`.
+///
+/// ```
+/// use xor_name::{closest_first_bounded, xor_name};
+///
+/// let peers = [xor_name!(0b1000_0000), xor_name!(0b0110_0000), xor_name!(0b0100_0001)];
+/// let target = xor_name!(0b0100_0000);
+/// let closest: Vec<_> = closest_first_bounded(&peers, &target, 2).collect();
+/// assert_eq!(closest, [xor_name!(0b0100_0001), xor_name!(0b0110_0000)]);
+/// ```
+pub fn closest_first_bounded<I>(names: I, target: &XorName, k: usize) -> ClosestFirst
+where
+    I: IntoIterator,
+    I::Item: Borrow<XorName>,
+{
+    // A max-heap of distances, so the farthest of the `k` closest is replaced first.
+    let mut closest = BinaryHeap::with_capacity(k);
+    for name in names {
+        let distance = target.distance(name.borrow());
+        if closest.len() < k {
+            closest.push(distance);
+        } else if closest.peek().is_some_and(|farthest| distance < *farthest) {
+            let _ = closest.pop();
+            closest.push(distance);
+        }
+    }
+    ClosestFirst {
+        target: *target,
+        heap: closest.into_iter().map(Reverse).collect(),
+    }
+}
+
+/// Iterator over names in increasing distance from a target, created by [`closest_first`] and
+/// [`closest_first_bounded`].
+pub struct ClosestFirst {
+    target: XorName,
+    heap: BinaryHeap<Reverse<XorDistance>>,
+}
+
+impl Iterator for ClosestFirst {
+    type Item = XorName;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse(distance) = self.heap.pop()?;
+        Some(DistanceFrom(&self.target).name(&distance))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl ExactSizeIterator for ClosestFirst {}
+
+impl FusedIterator for ClosestFirst {}
+
+/// Simulates greedy routing from the section of `source` to the section of `target`, where each
+/// section only knows its neighbours: the sections whose prefixes differ from its own in exactly
+/// one bit.
+///
+/// Every hop goes to the neighbour closest to `target`. Returns the sections visited, starting
+/// with the one matching `source` and ending with the one matching `target`, so the hop count
+/// is one less than the length. Returns `None` if no prefix matches `source`, or if routing gets
+/// stuck because no neighbour is closer to `target`, which can only happen if `prefixes` doesn't
+/// cover the whole name space.
+pub fn greedy_path(prefixes: &[Prefix], source: &XorName, target: &XorName) -> Option<Vec<Prefix>> {
+    let mut current = *prefixes.iter().find(|prefix| prefix.matches(source))?;
+    let mut path = vec![current];
+
+    while !current.matches(target) {
+        let next = prefixes
+            .iter()
+            .filter(|prefix| prefix.is_neighbour(&current))
+            .min_by(|lhs, rhs| {
+                lhs.min_distance_to(target)
+                    .cmp(&rhs.min_distance_to(target))
+                    .then_with(|| lhs.cmp_breadth_first(rhs))
+            })?;
+        // Each hop must get strictly closer, which also guarantees termination.
+        if next.min_distance_to(target) >= current.min_distance_to(target) {
+            return None;
+        }
+        current = *next;
+        path.push(current);
+    }
+
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn distance() {
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let (a, b): (XorName, XorName) = (rng.gen(), rng.gen());
+            assert_eq!(a.distance(&b), b.distance(&a));
+            assert_eq!(a.distance(&a), XorDistance::default());
+            assert_eq!(a.distance(&b).leading_zeros(), a.common_prefix(&b));
+        }
+        assert_eq!(XorDistance::default().leading_zeros(), 256);
+        assert_eq!(
+            xor_name!(0, 0b0001_0000)
+                .distance(&xor_name!())
+                .leading_zeros(),
+            11
+        );
+    }
+
+    #[test]
+    fn k_closest_matches_full_sort() {
+        let mut rng = SmallRng::from_entropy();
+
+        for &len in &[0, 1, 2, 10, 1000] {
+            let mut names: BTreeSet<XorName> = (0..len).map(|_| rng.gen()).collect();
+            // Names sharing long prefixes with each other force deep splits.
+            let base: XorName = rng.gen();
+            for i in 0..10 {
+                let _ = names.insert(base.with_flipped_bit(250 + i % 6).with_flipped_bit(i));
+            }
+
+            let target = if rng.gen() {
+                *names.iter().next().unwrap()
+            } else {
+                base.with_flipped_bit(255)
+            };
+            let mut sorted: Vec<XorName> = names.iter().copied().collect();
+            sorted.sort_by(|lhs, rhs| target.cmp_distance(lhs, rhs));
+
+            for &k in &[0, 1, 2, 5, 20, names.len(), names.len() + 1] {
+                let expected = &sorted[..k.min(sorted.len())];
+                assert_eq!(k_closest(&names, &target, k), expected, "k = {}", k);
+            }
+        }
+    }
+
+    #[test]
+    fn closest_first_matches_full_sort() {
+        let mut rng = SmallRng::from_entropy();
+        let target: XorName = rng.gen();
+        let mut names: Vec<XorName> = (0..500).map(|_| rng.gen()).collect();
+        names.extend_from_within(..10);
+        names.push(target);
+
+        let mut sorted = names.clone();
+        sorted.sort_by(|lhs, rhs| target.cmp_distance(lhs, rhs));
+        let mut closest = closest_first(names.iter(), &target);
+        assert_eq!(closest.len(), names.len());
+        assert_eq!(closest.by_ref().take(5).collect::<Vec<_>>(), sorted[..5]);
+        assert_eq!(closest.len(), names.len() - 5);
+        assert_eq!(closest.collect::<Vec<_>>(), sorted[5..]);
+
+        assert_eq!(closest_first(names, &target).next(), Some(target));
+        assert_eq!(closest_first(Vec::<XorName>::new(), &target).next(), None);
+    }
+
+    #[test]
+    fn closest_first_bounded_matches_full_sort() {
+        let mut rng = SmallRng::from_entropy();
+        let target: XorName = rng.gen();
+        let mut names: Vec<XorName> = (0..500).map(|_| rng.gen()).collect();
+        names.extend_from_within(..10);
+        names.push(target);
+
+        let mut sorted = names.clone();
+        sorted.sort_by(|lhs, rhs| target.cmp_distance(lhs, rhs));
+        for &k in &[0, 1, 5, 100, names.len(), names.len() + 1] {
+            let closest = closest_first_bounded(names.iter(), &target, k);
+            assert_eq!(closest.len(), k.min(names.len()));
+            assert_eq!(
+                closest.collect::<Vec<_>>(),
+                sorted[..k.min(names.len())],
+                "k = {}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn greedy_path() {
+        let prefixes: Vec<Prefix> = ["000", "001", "01", "10", "110", "111"]
+            .iter()
+            .map(|bits| bits.parse().unwrap())
+            .collect();
+        let name = |byte: u8| XorName([byte; XOR_NAME_LEN]);
+        let path = |source, target| {
+            super::greedy_path(&prefixes, &name(source), &name(target))
+                .map(|path| path.iter().map(|p| p.to_string()).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            path(0b0000_0000, 0b0001_1111),
+            Some(vec!["000".to_string()])
+        );
+        assert_eq!(
+            path(0b0000_0000, 0b1111_1111).unwrap(),
+            ["000", "10", "111"]
+        );
+        assert_eq!(path(0b0100_0000, 0b0010_0000).unwrap(), ["01", "001"]);
+
+        // A gap in the name space: nothing covers `11`, so routing towards it gets stuck.
+        let partial = &prefixes[..4];
+        assert_eq!(
+            super::greedy_path(partial, &name(0), &name(0b1111_1111)),
+            None
+        );
+        assert_eq!(
+            super::greedy_path(partial, &name(0b1100_0000), &name(0)),
+            None
+        );
+
+        // In a complete partition, every hop fixes at least one more bit towards the target.
+        let mut rng = SmallRng::from_entropy();
+        let mut prefixes = vec![Prefix::default()];
+        for _ in 0..50 {
+            let i = rng.gen_range(0..prefixes.len());
+            let prefix = prefixes.swap_remove(i);
+            prefixes.push(prefix.pushed(false));
+            prefixes.push(prefix.pushed(true));
+        }
+        for _ in 0..100 {
+            let (source, target): (XorName, XorName) = (rng.gen(), rng.gen());
+            let path = super::greedy_path(&prefixes, &source, &target).unwrap();
+            assert!(path[0].matches(&source));
+            assert!(path[path.len() - 1].matches(&target));
+            for hop in path.windows(2) {
+                assert!(hop[0].is_neighbour(&hop[1]));
+                assert!(hop[1].common_prefix(&target) > hop[0].common_prefix(&target));
+            }
+        }
+    }
+
+    #[test]
+    fn distance_order_matches_cmp_distance() {
+        let mut rng = SmallRng::from_entropy();
+        let target: XorName = rng.gen();
+        let by_distance = DistanceFrom(&target);
+
+        let mut names: Vec<XorName> = (0..100).map(|_| rng.gen()).collect();
+        names.push(target);
+        for lhs in &names {
+            for rhs in &names {
+                assert_eq!(
+                    by_distance.key(lhs).cmp(&by_distance.key(rhs)),
+                    target.cmp_distance(lhs, rhs)
+                );
+                assert_eq!(by_distance.cmp(lhs, rhs), target.cmp_distance(lhs, rhs));
+                assert_eq!(lhs.distance_key(&target), by_distance.key(lhs));
+            }
+            assert_eq!(by_distance.name(&by_distance.key(lhs)), *lhs);
+        }
+
+        let mut by_key = names.clone();
+        by_key.sort_by_key(|name| name.distance_key(&target));
+        names.sort_by(|lhs, rhs| by_distance.cmp(lhs, rhs));
+        assert_eq!(by_key, names);
+        assert_eq!(names[0], target);
+    }
+}
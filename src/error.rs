@@ -0,0 +1,89 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::XOR_NAME_LEN;
+use core::fmt::{self, Display, Formatter};
+
+/// The error type for fallible operations in this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A prefix string contained a character other than `0` or `1`.
+    InvalidChar(char),
+    /// A prefix string was longer than the 256 bits of a name. Contains the actual length.
+    TooLong(usize),
+    /// A bit index or bit count was beyond the 256 bits of a name.
+    BitIndexOutOfRange(usize),
+    /// A byte sequence of the wrong length was converted.
+    InvalidLength {
+        /// The number of bytes required.
+        expected: usize,
+        /// The number of bytes given.
+        actual: usize,
+    },
+    /// A conversion would have discarded significant bits.
+    LossyConversion,
+    /// A prefix shorter than 256 bits was converted to a name. Contains its bit count.
+    IncompletePrefix(usize),
+    /// Two name sketches of different prefixes or shapes were merged.
+    IncompatibleSketch,
+    /// A deserialized name sketch had a number of words or hash functions outside the limits
+    /// of `NameSketch`.
+    InvalidSketch,
+    /// Prefixes that were required to be disjoint overlapped.
+    OverlappingPrefixes,
+    /// A name string was not exactly 64 hex digits.
+    InvalidHex,
+    /// An ancestor was requested with a bit count not less than the prefix's own. Contains the
+    /// requested bit count.
+    NotAnAncestor(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidChar(c) => write!(f, "expected `0` or `1`, but encountered `{}`", c),
+            Error::TooLong(len) => write!(
+                f,
+                "max length exceeded {} with length of {}",
+                XOR_NAME_LEN * 8,
+                len
+            ),
+            Error::BitIndexOutOfRange(i) => write!(
+                f,
+                "bit index {} out of range for {} bits",
+                i,
+                XOR_NAME_LEN * 8
+            ),
+            Error::InvalidLength { expected, actual } => {
+                write!(f, "expected {} bytes, got {}", expected, actual)
+            }
+            Error::LossyConversion => write!(f, "conversion would discard significant bits"),
+            Error::IncompletePrefix(bit_count) => write!(
+                f,
+                "prefix of {} bits does not determine a name of {} bits",
+                bit_count,
+                XOR_NAME_LEN * 8
+            ),
+            Error::IncompatibleSketch => {
+                write!(f, "sketches differ in prefix, size or number of hashes")
+            }
+            Error::InvalidSketch => {
+                write!(f, "sketch has too few or too many words or hashes")
+            }
+            Error::OverlappingPrefixes => write!(f, "prefixes overlap"),
+            Error::InvalidHex => write!(f, "expected {} hex digits", 2 * XOR_NAME_LEN),
+            Error::NotAnAncestor(bit_count) => {
+                write!(f, "a prefix of {} bits is not an ancestor", bit_count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
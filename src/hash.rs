@@ -0,0 +1,87 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use core::hash::{BuildHasherDefault, Hasher};
+
+/// A `Hasher` for maps and sets keyed by `XorName`.
+///
+/// Names are already uniformly distributed, so instead of running them through SipHash this
+/// hasher uses their first 8 bytes directly as the hash. Use it via [`BuildXorNameHasher`]:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use xor_name::{BuildXorNameHasher, XorName};
+///
+/// let mut map: HashMap<XorName, u32, BuildXorNameHasher> = HashMap::default();
+/// let _ = map.insert(XorName::from_content(b"abc"), 1);
+/// ```
+///
+/// It is only suitable for keys whose leading bytes are uniformly random, such as names derived
+/// from content or keys. In particular it doesn't protect against HashDoS when the names can be
+/// chosen by an attacker, since they can easily produce names sharing their first 8 bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XorNameHasher(u64);
+
+impl Hasher for XorNameHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut word = [0; 8];
+        let len = bytes.len().min(word.len());
+        word[..len].copy_from_slice(&bytes[..len]);
+        // Mixing the previous state in keeps the hasher usable for keys that are hashed in
+        // several `write` calls (e.g. the length prefix written before the bytes of an array).
+        self.0 = self.0.rotate_left(8) ^ u64::from_ne_bytes(word);
+    }
+}
+
+/// `BuildHasher` creating [`XorNameHasher`]s.
+pub type BuildXorNameHasher = BuildHasherDefault<XorNameHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorName;
+    use core::hash::BuildHasher;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn map_with_name_hasher() {
+        let mut rng = SmallRng::from_entropy();
+        let names: Vec<XorName> = (0..1000).map(|_| rng.gen()).collect();
+
+        let mut map: HashMap<XorName, usize, BuildXorNameHasher> = HashMap::default();
+        for (i, name) in names.iter().enumerate() {
+            let _ = map.insert(*name, i);
+        }
+
+        assert_eq!(map.len(), names.len());
+        for (i, name) in names.iter().enumerate() {
+            assert_eq!(map.get(name), Some(&i));
+        }
+    }
+
+    #[test]
+    fn hash_depends_on_leading_bytes_only() {
+        let build = BuildXorNameHasher::default();
+        let hash = |name: &XorName| build.hash_one(name);
+
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let mut same_lead = name;
+        same_lead.0[8..].copy_from_slice(&rng.gen::<[u8; 24]>());
+        assert_eq!(hash(&name), hash(&same_lead));
+
+        let hashes: HashSet<u64> = (0..1000).map(|_| hash(&rng.gen())).collect();
+        assert_eq!(hashes.len(), 1000);
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Conversions between `XorName` and identifiers of other XOR-metric networks.
+//!
+//! # Kademlia node IDs
+//!
+//! Kademlia and the BitTorrent mainline DHT use 160-bit (20 byte) IDs. An ID is mapped to the
+//! name starting with the same 20 bytes, followed by 12 zero bytes. This keeps the bits in the
+//! same positions, so common prefixes, XOR distance order and [`Prefix`](crate::Prefix) matching
+//! carry over between the two spaces unchanged.
+//!
+//! Going back, [`to_kademlia_id`] keeps the first 20 bytes and drops the rest, which loses
+//! information for names not created from an ID. [`try_to_kademlia_id`] only accepts names whose
+//! last 12 bytes are zero, so that `from_kademlia_id` and it are exact inverses.
+
+use crate::{Error, XorName, XOR_NAME_LEN};
+
+/// Length in bytes of a Kademlia node ID.
+pub const KADEMLIA_ID_LEN: usize = 20;
+
+/// Converts a Kademlia node ID to the name with the same leading 20 bytes and zeroes after them.
+pub fn from_kademlia_id(id: &[u8; KADEMLIA_ID_LEN]) -> XorName {
+    let mut name = XorName::ZERO;
+    name.0[..KADEMLIA_ID_LEN].copy_from_slice(id);
+    name
+}
+
+/// Returns the first 20 bytes of `name` as a Kademlia node ID. The last 12 bytes are dropped.
+pub fn to_kademlia_id(name: &XorName) -> [u8; KADEMLIA_ID_LEN] {
+    let mut id = [0; KADEMLIA_ID_LEN];
+    id.copy_from_slice(&name.0[..KADEMLIA_ID_LEN]);
+    id
+}
+
+/// Returns the first 20 bytes of `name` as a Kademlia node ID, or
+/// [`Error::LossyConversion`] if any of the last 12 bytes is nonzero.
+pub fn try_to_kademlia_id(name: &XorName) -> Result<[u8; KADEMLIA_ID_LEN], Error> {
+    if name.0[KADEMLIA_ID_LEN..XOR_NAME_LEN]
+        .iter()
+        .any(|byte| *byte != 0)
+    {
+        return Err(Error::LossyConversion);
+    }
+    Ok(to_kademlia_id(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn kademlia_roundtrip() {
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let id: [u8; KADEMLIA_ID_LEN] = rng.gen();
+            let name = from_kademlia_id(&id);
+            assert_eq!(&name[..KADEMLIA_ID_LEN], &id[..]);
+            assert!(name[KADEMLIA_ID_LEN..].iter().all(|byte| *byte == 0));
+            assert_eq!(to_kademlia_id(&name), id);
+            assert_eq!(try_to_kademlia_id(&name), Ok(id));
+        }
+    }
+
+    #[test]
+    fn kademlia_lossy() {
+        let mut name = from_kademlia_id(&[0xAB; KADEMLIA_ID_LEN]);
+        name.0[XOR_NAME_LEN - 1] = 1;
+        assert_eq!(to_kademlia_id(&name), [0xAB; KADEMLIA_ID_LEN]);
+        assert_eq!(try_to_kademlia_id(&name), Err(Error::LossyConversion));
+    }
+
+    #[test]
+    fn kademlia_preserves_distance_order() {
+        let mut rng = SmallRng::from_entropy();
+        let ids: Vec<[u8; KADEMLIA_ID_LEN]> = (0..50).map(|_| rng.gen()).collect();
+        let target = ids[0];
+        for lhs in &ids {
+            for rhs in &ids {
+                let distance = |id: &[u8; KADEMLIA_ID_LEN]| {
+                    let mut d = *id;
+                    d.iter_mut().zip(&target).for_each(|(d, t)| *d ^= t);
+                    d
+                };
+                assert_eq!(
+                    from_kademlia_id(&target)
+                        .cmp_distance(&from_kademlia_id(lhs), &from_kademlia_id(rhs)),
+                    distance(lhs).cmp(&distance(rhs))
+                );
+            }
+        }
+    }
+}
@@ -10,6 +10,15 @@
 //! # xor_name
 //!
 //! TODO requires further documentation.
+//!
+//! ## Panics
+//!
+//! No function in this crate panics on any input, unless it documents so in a "Panics"
+//! section, and each of those has a non-panicking alternative: [`Prefix::ancestor`] has
+//! [`Prefix::try_ancestor`]. The [`prefix!`] and [`xor_name!`] macros reject invalid input at
+//! compile time instead of panicking. The assertions in the optional `test_utils` module panic by
+//! design. Allocating functions, such as those collecting names into a `Vec`, abort on
+//! allocation failure like the standard collections do.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/maidsafe/QA/master/Images/maidsafe_logo.png",
@@ -47,37 +56,127 @@
     unused_results
 )]
 #![allow(
-    box_pointers,
     missing_copy_implementations,
     missing_debug_implementations,
     variant_size_differences
 )]
 
-use core::{cmp::Ordering, fmt, ops};
-pub use prefix::Prefix;
+pub use accumulator::{xor_all, XorAccumulator};
+pub use balance::NameDistribution;
+#[cfg(feature = "bytes")]
+pub use buffers::{names_from_bytes, names_to_bytes};
+pub use builder::XorNameBuilder;
+pub use compact::CompactPrefix;
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    mem::{align_of, size_of},
+    ops,
+    str::FromStr,
+};
+pub use distance::{
+    closest_first, closest_first_bounded, greedy_path, k_closest, ClosestFirst, DistanceFrom,
+    XorDistance,
+};
+pub use error::Error;
+pub use hash::{BuildXorNameHasher, XorNameHasher};
+pub use prefix::{
+    all_prefixes, all_prefixes_from, Ancestors, BreadthFirst, Names, Prefix, Subtree,
+};
 pub use rand;
 use rand::distributions::{Distribution, Standard};
+pub use range_map::XorRangeMap;
+#[cfg(feature = "sketch")]
+pub use sketch::NameSketch;
+pub use split::{recommend_split, SplitAdvice};
 use tiny_keccak::{Hasher, Sha3};
 
 /// Creates XorName with the given leading bytes and the rest filled with zeroes.
+///
+/// The expansion is const-evaluable, so the macro can be used to define constants:
+///
+/// ```
+/// use xor_name::{xor_name, XorName};
+///
+/// const WELL_KNOWN: XorName = xor_name!(0xAA, 0xBB);
+/// assert_eq!(&WELL_KNOWN[..3], &[0xAA, 0xBB, 0x00]);
+/// ```
+///
+/// Passing more than 32 bytes, or arguments that aren't `u8`, fails to compile:
+///
+/// ```compile_fail
+/// # use xor_name::xor_name;
+/// let _ = xor_name!(
+///     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+///     25, 26, 27, 28, 29, 30, 31, 32
+/// );
+/// ```
+///
+/// ```compile_fail
+/// # use xor_name::xor_name;
+/// let _ = xor_name!(1u16);
+/// ```
 #[macro_export]
 macro_rules! xor_name {
-    ($($byte:expr),* $(,)?) => {{
-        let mut name = $crate::XorName::default();
-        let mut index = 0;
+    () => {
+        $crate::XorName::ZERO
+    };
+    ($($byte:expr),+ $(,)?) => {{
+        const COUNT: usize = <[()]>::len(&[$($crate::__xor_name_unit!($byte)),+]);
+        const _: () = assert!(
+            COUNT <= $crate::XOR_NAME_LEN,
+            "xor_name! takes at most 32 bytes"
+        );
+        let bytes: [u8; COUNT] = [$($byte),+];
 
-        #[allow(unused_assignments)]
-        {
-            $(
-                name.0[index] = $byte;
-                index += 1;
-            )*
+        let mut name = $crate::XorName::ZERO;
+        let mut index = 0;
+        while index < COUNT {
+            name.0[index] = bytes[index];
+            index += 1;
         }
 
         name
     }}
 }
 
+// Counts the arguments of `xor_name!` at compile time, without evaluating them.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __xor_name_unit {
+    ($byte:expr) => {
+        ()
+    };
+}
+
+/// Creates a `Prefix` from a string of `0`s and `1`s at compile time.
+///
+/// The string is parsed like [`Prefix::from_str`](core::str::FromStr::from_str), so `_` may be
+/// used to separate groups of bits. Unlike that, invalid characters or more than 256 bits are
+/// compile errors, and the macro can be used to define constants:
+///
+/// ```
+/// use xor_name::{prefix, Prefix};
+///
+/// const LEFT: Prefix = prefix!("0101_1");
+/// assert_eq!(LEFT, "01011".parse().unwrap());
+/// assert_eq!(prefix!(""), Prefix::default());
+/// ```
+///
+/// ```compile_fail
+/// # use xor_name::prefix;
+/// let _ = prefix!("0120");
+/// ```
+#[macro_export]
+macro_rules! prefix {
+    ($bits:expr) => {{
+        const PREFIX: $crate::Prefix = $crate::Prefix::__parse_const($bits);
+        PREFIX
+    }};
+}
+
 // No-std replacement for std::format! macro which returns `ArrayString` instead of `String`. The
 // capacity of the returned `ArrayString` needs to explicitly given as the first argument.
 #[cfg(test)]
@@ -89,13 +188,43 @@ macro_rules! format {
     }}
 }
 
+mod accumulator;
+mod balance;
+#[cfg(feature = "bytes")]
+mod buffers;
+mod builder;
+mod compact;
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+mod db;
+mod distance;
+mod error;
+mod hash;
+pub mod interop;
+#[cfg(feature = "rayon")]
+pub mod par;
 mod prefix;
+#[cfg(feature = "python")]
+pub mod python;
+mod range_map;
+pub mod serde_helpers;
 #[cfg(feature = "serialize-hex")]
 mod serialize;
+#[cfg(feature = "sketch")]
+mod sketch;
+mod split;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "ufmt")]
+mod uformat;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Constant byte length of `XorName`.
 pub const XOR_NAME_LEN: usize = 32;
 
+// Number of `u64` words in a `XorName`.
+const WORD_COUNT: usize = XOR_NAME_LEN / 8;
+
 /// A 256-bit number, viewed as a point in XOR space.
 ///
 /// This wraps an array of 32 bytes, i. e. a number between 0 and 2<sup>256</sup> - 1.
@@ -103,15 +232,38 @@ pub const XOR_NAME_LEN: usize = 32;
 /// XOR space is the space of these numbers, with the [XOR metric][1] as a notion of distance,
 /// i. e. the points with IDs `x` and `y` are considered to have distance `x xor y`.
 ///
+/// The `Default` value is [`XorName::ZERO`].
+///
+/// # Layout
+///
+/// `XorName` is `#[repr(transparent)]`: it is guaranteed to have the same size, alignment and
+/// ABI as `[u8; 32]`, i.e. 32 bytes aligned to 1, in the same order as the bytes of the array.
+/// Pointers to a `[u8; 32]` can therefore be cast to pointers to a `XorName`, e.g. to read names
+/// directly from C buffers or memory-mapped files. This guarantee is part of the public API.
+///
 /// [1]: https://en.wikipedia.org/wiki/Kademlia#System_details
 #[derive(Eq, Copy, Clone, Default, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(
     not(feature = "serialize-hex"),
     derive(serde::Serialize, serde::Deserialize)
 )]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary)
+)]
+#[repr(transparent)]
 pub struct XorName(pub [u8; XOR_NAME_LEN]);
 
+// The layout promised above.
+const _: () = assert!(size_of::<XorName>() == XOR_NAME_LEN);
+const _: () = assert!(align_of::<XorName>() == 1);
+
 impl XorName {
+    /// The name with all bits set to `0`, i. e. the number 0. This is the smallest name, and the
+    /// one returned by `XorName::default()`.
+    pub const ZERO: Self = Self([0; XOR_NAME_LEN]);
+
     /// Generate a XorName for the given content.
     pub fn from_content(content: &[u8]) -> Self {
         Self::from_content_parts(&[content])
@@ -135,6 +287,51 @@ impl XorName {
         Self(xor)
     }
 
+    /// Generates a random name matching `prefix`.
+    pub fn generate_matching<T: rand::Rng>(prefix: &Prefix, rng: &mut T) -> Self {
+        prefix.substituted_in(Self::random(rng))
+    }
+
+    /// Searches for a name satisfying `predicate` among the candidates `candidate(0)`,
+    /// `candidate(1)`, ... up to `candidate(effort - 1)`, in parallel. Returns the first
+    /// matching candidate with its index, or `None` if none of them matches.
+    ///
+    /// The result is the one with the lowest index, however the work is scheduled, so it is
+    /// reproducible. Deriving the candidates from a counter makes the search deterministic, e.g.
+    /// hashing some content together with the index:
+    ///
+    /// ```
+    /// use xor_name::{prefix, XorName};
+    ///
+    /// let wanted = prefix!("1010_1");
+    /// let (nonce, name) = XorName::generate_where(
+    ///     |nonce| XorName::from_content_parts(&[b"my key", &nonce.to_be_bytes()]),
+    ///     |name| wanted.matches(name),
+    ///     10_000,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(name, XorName::from_content_parts(&[b"my key", &nonce.to_be_bytes()]));
+    /// assert!(wanted.matches(&name));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn generate_where<C, P>(candidate: C, predicate: P, effort: u64) -> Option<(u64, Self)>
+    where
+        C: Fn(u64) -> Self + Sync,
+        P: Fn(&Self) -> bool + Sync,
+    {
+        use rayon::prelude::*;
+
+        (0..effort)
+            .into_par_iter()
+            .map(|i| (i, candidate(i)))
+            .find_first(|(_, name)| predicate(name))
+    }
+
+    /// Returns `true` if this is [`XorName::ZERO`].
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
     /// Returns `true` if the `i`-th bit is `1`.
     pub fn bit(&self, i: u8) -> bool {
         let index = i / 8;
@@ -142,18 +339,151 @@ impl XorName {
         self[index as usize] & pow_i != 0
     }
 
+    /// Returns the bits in `range` as an integer, with the bit at `range.start` as the most
+    /// significant one. E.g. `bit_slice(12..28)` is the 16 bit number formed by bits 12 to 27.
+    ///
+    /// At most 64 bits are returned: if the range is longer, only its first 64 bits are used.
+    /// Bits past the end of the name read as `0`, and an empty range gives `0`.
+    pub fn bit_slice(&self, range: ops::Range<usize>) -> u64 {
+        let end = range.end.min(range.start.saturating_add(64));
+        if range.start >= end {
+            return 0;
+        }
+
+        // The window of up to 64 bits spans at most 9 bytes.
+        let first = range.start / 8;
+        let mut window = [0; 16];
+        for (i, byte) in window[..9].iter_mut().enumerate() {
+            *byte = self.0.get(first.saturating_add(i)).copied().unwrap_or(0);
+        }
+        let window = u128::from_be_bytes(window) << (range.start % 8);
+        (window >> (128 - (end - range.start))) as u64
+    }
+
+    /// Returns an abbreviated, human-checkable code for this name: its first `len` hex digits
+    /// (at most 64), a `-`, and a 4 digit checksum of the whole name, e.g. `"a1b2c3-9f0e"`.
+    ///
+    /// Use [`XorName::matches_short_code`] to check a code someone read out against a name.
+    pub fn short_code(&self, len: usize) -> String {
+        std::format!(
+            "{:1$x}-{2:04x}",
+            self,
+            len.min(2 * XOR_NAME_LEN),
+            self.checksum()
+        )
+    }
+
+    /// Returns `true` if `code` is a short code of this name, as produced by
+    /// [`XorName::short_code`] with any length. Hex digits may be in either case.
+    pub fn matches_short_code(&self, code: &str) -> bool {
+        let (digits, checksum) = match code.rsplit_once('-') {
+            Some(parts) => parts,
+            None => return false,
+        };
+        let full = std::format!("{:x}", self);
+        digits.len() <= full.len()
+            && digits.eq_ignore_ascii_case(&full[..digits.len()])
+            && checksum.len() == 4
+            && checksum.bytes().all(|byte| byte.is_ascii_hexdigit())
+            && u16::from_str_radix(checksum, 16) == Ok(self.checksum())
+    }
+
+    // A 16 bit checksum of the whole name, for short codes.
+    fn checksum(&self) -> u16 {
+        let mut sha3 = Sha3::v256();
+        sha3.update(b"xor_name short code");
+        sha3.update(&self.0);
+        let mut hash = [0; 2];
+        sha3.finalize(&mut hash);
+        u16::from_be_bytes(hash)
+    }
+
+    /// Returns the prefix of this name with the given number of bits. Same as
+    /// `Prefix::new(bit_count, name)`, so a `bit_count` above 256 gives the whole name.
+    pub fn prefix_of(&self, bit_count: usize) -> Prefix {
+        Prefix::new(bit_count, *self)
+    }
+
+    /// Returns an iterator over the names in `range`, starting at its start, each `step` (read as
+    /// a 256-bit number) greater than the previous one, up to at most its end.
+    ///
+    /// A zero `step` yields only the start, and an empty range yields nothing. The sweep can be
+    /// stopped and resumed later via [`Names::remaining`].
+    ///
+    /// ```
+    /// use xor_name::{xor_name, XorName};
+    ///
+    /// let step = xor_name!(0x10);
+    /// let mut names = XorName::names_between(xor_name!(0x08)..=xor_name!(0x40), step);
+    /// assert_eq!(names.next(), Some(xor_name!(0x08)));
+    /// assert_eq!(names.next(), Some(xor_name!(0x18)));
+    ///
+    /// // Resume the sweep where it was stopped.
+    /// let remaining = names.remaining().unwrap();
+    /// let rest: Vec<_> = XorName::names_between(remaining, step).collect();
+    /// assert_eq!(rest, [xor_name!(0x28), xor_name!(0x38)]);
+    /// ```
+    pub fn names_between(range: ops::RangeInclusive<XorName>, step: XorName) -> Names {
+        Names::new(range, step)
+    }
+
+    /// Compresses the name into 64 bits by XOR-ing its four 8 byte words together.
+    ///
+    /// Every bit of the name affects the result, so names sharing a long prefix still get
+    /// unrelated values, and for uniformly random names the result is uniformly distributed too.
+    /// That makes it suitable for shard keys, sampling or metric labels. It is not a
+    /// cryptographic hash: names can easily be chosen to fold to the same value.
+    pub fn fold_to_u64(&self) -> u64 {
+        (0..WORD_COUNT).fold(0, |folded, i| folded ^ self.word(i))
+    }
+
+    /// Compresses the name into 32 bits by XOR-ing the two halves of
+    /// [`XorName::fold_to_u64`]. The same properties apply.
+    pub fn fold_to_u32(&self) -> u32 {
+        let folded = self.fold_to_u64();
+        (folded >> 32) as u32 ^ folded as u32
+    }
+
     /// Compares the distance of the arguments to `self`. Returns `Less` if `lhs` is closer,
     /// `Greater` if `rhs` is closer, and `Equal` if `lhs == rhs`. (The XOR distance can only be
     /// equal if the arguments are equal.)
+    #[inline]
     pub fn cmp_distance(&self, lhs: &Self, rhs: &Self) -> Ordering {
-        for i in 0..XOR_NAME_LEN {
-            if lhs[i] != rhs[i] {
-                return Ord::cmp(&(lhs[i] ^ self[i]), &(rhs[i] ^ self[i]));
+        for i in 0..WORD_COUNT {
+            let (lhs, rhs) = (lhs.word(i), rhs.word(i));
+            if lhs != rhs {
+                let target = self.word(i);
+                return Ord::cmp(&(lhs ^ target), &(rhs ^ target));
             }
         }
         Ordering::Equal
     }
 
+    /// Returns the XOR distance between `self` and `other`.
+    pub fn distance(&self, other: &Self) -> XorDistance {
+        let mut distance = [0; XOR_NAME_LEN];
+        for (d, (lhs, rhs)) in distance.iter_mut().zip(self.0.iter().zip(other.0.iter())) {
+            *d = lhs ^ rhs;
+        }
+        XorDistance(distance)
+    }
+
+    /// Returns the distance of `self` from `target`, as a key for sorting names or for sorted
+    /// containers. Closer names have smaller keys.
+    ///
+    /// ```
+    /// use xor_name::{xor_name, XorName};
+    ///
+    /// let target = xor_name!(0b0100_0000);
+    /// let (near, mid, far) = (xor_name!(0b0110_0000), xor_name!(0), xor_name!(0b1000_0000));
+    /// let mut names = vec![far, mid, near];
+    /// names.sort_by_key(|name| name.distance_key(&target));
+    /// assert_eq!(names, [near, mid, far]);
+    /// ```
+    pub fn distance_key(&self, target: &Self) -> XorDistance {
+        target.distance(self)
+    }
+
     /// Returns a copy of `self`, with the `i`-th bit set to `bit`.
     ///
     /// If `i` exceeds the number of bits in `self`, an unmodified copy of `self` is returned.
@@ -183,10 +513,8 @@ impl XorName {
 
     /// Returns a copy of self with first `n` bits preserved, and remaining bits
     /// set to 0 (val == false) or 1 (val == true).
-    fn set_remaining(mut self, n: u8, val: bool) -> Self {
+    fn set_remaining(mut self, n: usize, val: bool) -> Self {
         for (i, x) in self.0.iter_mut().enumerate() {
-            let i = i as u8;
-
             if n <= i * 8 {
                 *x = if val { !0 } else { 0 };
             } else if n < (i + 1) * 8 {
@@ -202,21 +530,105 @@ impl XorName {
         self
     }
 
+    /// Returns `self + other` as 256-bit numbers, or `None` on overflow.
+    fn checked_add(mut self, other: &Self) -> Option<Self> {
+        let mut carry = false;
+        for (lhs, rhs) in self.0.iter_mut().zip(other.0.iter()).rev() {
+            let (sum, overflow1) = lhs.overflowing_add(*rhs);
+            let (sum, overflow2) = sum.overflowing_add(carry as u8);
+            *lhs = sum;
+            carry = overflow1 || overflow2;
+        }
+        if carry {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Returns `self - other` as 256-bit numbers, wrapping around on underflow.
+    fn wrapping_sub(mut self, other: &Self) -> Self {
+        let mut borrow = false;
+        for (lhs, rhs) in self.0.iter_mut().zip(other.0.iter()).rev() {
+            let (difference, underflow1) = lhs.overflowing_sub(*rhs);
+            let (difference, underflow2) = difference.overflowing_sub(borrow as u8);
+            *lhs = difference;
+            borrow = underflow1 || underflow2;
+        }
+        self
+    }
+
+    /// Returns the quotient and the remainder of `self / divisor` as 256-bit numbers.
+    ///
+    /// `divisor` must not be zero.
+    fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        debug_assert!(!divisor.is_zero());
+        let mut quotient = Self::ZERO;
+        let mut remainder = Self::ZERO;
+        for i in 0..8 * XOR_NAME_LEN {
+            // Shift the next bit of `self` into the remainder, which may then need 257 bits.
+            let mut carry = self.bit(i as u8) as u8;
+            for byte in remainder.0.iter_mut().rev() {
+                let shifted_out = *byte >> 7;
+                *byte = *byte << 1 | carry;
+                carry = shifted_out;
+            }
+            if carry == 1 || remainder >= *divisor {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient = quotient.with_bit(i as u8, true);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    /// Returns the name following `self` in numerical order, or `None` if `self` is the largest
+    /// possible name.
+    fn successor(mut self) -> Option<Self> {
+        for byte in self.0.iter_mut().rev() {
+            if *byte == u8::MAX {
+                *byte = 0;
+            } else {
+                *byte += 1;
+                return Some(self);
+            }
+        }
+        None
+    }
+
     /// Returns the length of the common prefix with the `other` name; e. g.
     /// the when `other = 11110000` and `self = 11111111` this is 4.
     fn common_prefix(&self, other: &Self) -> usize {
-        for byte_index in 0..XOR_NAME_LEN {
-            if self[byte_index] != other[byte_index] {
-                return (byte_index * 8)
-                    + (self[byte_index] ^ other[byte_index]).leading_zeros() as usize;
+        for i in 0..WORD_COUNT {
+            let diff = self.word(i) ^ other.word(i);
+            if diff != 0 {
+                return i * 64 + diff.leading_zeros() as usize;
             }
         }
         8 * XOR_NAME_LEN
     }
+
+    /// Returns the `i`-th group of 8 bytes as a big-endian integer, so that comparing words
+    /// is equivalent to comparing the corresponding bytes lexicographically.
+    #[inline]
+    fn word(&self, i: usize) -> u64 {
+        let mut word = [0; 8];
+        word.copy_from_slice(&self.0[8 * i..8 * (i + 1)]);
+        u64::from_be_bytes(word)
+    }
 }
 
+/// The default format is abbreviated to the first three bytes and the first eight bits. The
+/// alternate format (`{:#?}`) shows the full name in hex, together with its leading bits.
 impl fmt::Debug for XorName {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if formatter.alternate() {
+            return formatter
+                .debug_struct("XorName")
+                .field("hex", &format_args!("{:x}", self))
+                .field("leading_bits", &format_args!("{:#16b}", self))
+                .finish();
+        }
+
         write!(
             formatter,
             "{:02x}{:02x}{:02x}({:08b})..",
@@ -234,6 +646,8 @@ impl fmt::Display for XorName {
 impl fmt::Binary for XorName {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         if let Some(width) = formatter.width() {
+            // Wider formats show no more than the whole name.
+            let width = width.min(8 * XOR_NAME_LEN);
             let whole_bytes = width / 8;
             let remaining_bits = width % 8;
 
@@ -259,7 +673,9 @@ impl fmt::Binary for XorName {
 
 impl fmt::LowerHex for XorName {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        let bytes = formatter.width().unwrap_or(2 * XOR_NAME_LEN) / 2;
+        let bytes = formatter
+            .width()
+            .map_or(XOR_NAME_LEN, |width| width.min(2 * XOR_NAME_LEN) / 2);
 
         for byte in &self[..bytes] {
             write!(formatter, "{:02x}", byte)?;
@@ -275,7 +691,9 @@ impl fmt::LowerHex for XorName {
 
 impl fmt::UpperHex for XorName {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        let bytes = formatter.width().unwrap_or(2 * XOR_NAME_LEN) / 2;
+        let bytes = formatter
+            .width()
+            .map_or(XOR_NAME_LEN, |width| width.min(2 * XOR_NAME_LEN) / 2);
 
         for byte in &self[..bytes] {
             write!(formatter, "{:02X}", byte)?;
@@ -320,6 +738,82 @@ impl AsRef<[u8]> for XorName {
     }
 }
 
+impl AsMut<[u8]> for XorName {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0[..]
+    }
+}
+
+impl Borrow<[u8; XOR_NAME_LEN]> for XorName {
+    fn borrow(&self) -> &[u8; XOR_NAME_LEN] {
+        &self.0
+    }
+}
+
+impl PartialEq<[u8; XOR_NAME_LEN]> for XorName {
+    fn eq(&self, other: &[u8; XOR_NAME_LEN]) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<XorName> for [u8; XOR_NAME_LEN] {
+    fn eq(&self, other: &XorName) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<&[u8]> for XorName {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.0[..] == **other
+    }
+}
+
+impl TryFrom<&[u8]> for XorName {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = <[u8; XOR_NAME_LEN]>::try_from(bytes).map_err(|_| Error::InvalidLength {
+            expected: XOR_NAME_LEN,
+            actual: bytes.len(),
+        })?;
+        Ok(Self(bytes))
+    }
+}
+
+/// Parses exactly 64 hex digits, in either case, as produced by the `{:x}` and `{:X}` formats.
+impl FromStr for XorName {
+    type Err = Error;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        let digits = hex.as_bytes();
+        if digits.len() != 2 * XOR_NAME_LEN {
+            return Err(Error::InvalidHex);
+        }
+        let mut name = Self::default();
+        for (byte, pair) in name.0.iter_mut().zip(digits.chunks_exact(2)) {
+            let digit = |digit: u8| (digit as char).to_digit(16).ok_or(Error::InvalidHex);
+            *byte = (digit(pair[0])? << 4 | digit(pair[1])?) as u8;
+        }
+        Ok(name)
+    }
+}
+
+impl TryFrom<&str> for XorName {
+    type Error = Error;
+
+    fn try_from(hex: &str) -> Result<Self, Self::Error> {
+        hex.parse()
+    }
+}
+
+impl TryFrom<String> for XorName {
+    type Error = Error;
+
+    fn try_from(hex: String) -> Result<Self, Self::Error> {
+        hex.parse()
+    }
+}
+
 impl ops::Deref for XorName {
     type Target = [u8];
 
@@ -328,6 +822,12 @@ impl ops::Deref for XorName {
     }
 }
 
+impl ops::DerefMut for XorName {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0[..]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -395,6 +895,15 @@ mod tests {
             &format!(18, "{:?}", xor_name!(0x89, 0xab, 0xcd, 0xdf)),
             "89abcd(10001001).."
         );
+
+        let name = xor_name!(0x89, 0xab, 0xcd, 0xdf);
+        assert_eq!(
+            &format!(200, "{:#?}", name),
+            "XorName {\n    \
+             hex: 89abcddf00000000000000000000000000000000000000000000000000000000,\n    \
+             leading_bits: 1000100110101011..,\n\
+             }"
+        );
     }
 
     #[test]
@@ -427,6 +936,11 @@ mod tests {
         // odd widths are truncated to nearest even
         assert_eq!(&format!(2, "{:3x}", xor_name!(0x01, 0x23, 0xab)), "01");
         assert_eq!(&format!(4, "{:5x}", xor_name!(0x01, 0x23, 0xab)), "0123");
+
+        // widths beyond the name show the whole name
+        let name = XorName([0xab; XOR_NAME_LEN]);
+        assert_eq!(&format!(64, "{:80x}", name), &format!(64, "{:x}", name));
+        assert_eq!(&format!(64, "{:#80X}", name), &format!(64, "{:X}", name));
     }
 
     #[test]
@@ -482,6 +996,11 @@ mod tests {
             &format!(10, "{:#8b}", xor_name!(0b00001111, 0b01010101)),
             "00001111.."
         );
+
+        // widths beyond the name show the whole name
+        let name = XorName([0xab; XOR_NAME_LEN]);
+        assert_eq!(&format!(256, "{:260b}", name), &format!(256, "{:b}", name));
+        assert_eq!(&format!(256, "{:#300b}", name), &format!(256, "{:b}", name));
     }
 
     #[test]
@@ -575,6 +1094,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn zero() {
+        assert_eq!(XorName::ZERO, XorName::default());
+        assert_eq!(XorName::ZERO, xor_name!());
+        assert!(XorName::ZERO.is_zero());
+        assert!(!xor_name!(0, 0, 1).is_zero());
+        assert!(!(!XorName::ZERO).is_zero());
+        assert!((0..8 * XOR_NAME_LEN).all(|i| !XorName::ZERO.bit(i as u8)));
+    }
+
     #[test]
     fn bit() {
         assert!(!xor_name!(0b00101000).bit(0));
@@ -590,6 +1119,110 @@ mod tests {
         assert!(!xor_name!(2, 128, 1, 0).bit(24));
     }
 
+    #[test]
+    fn short_code() {
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let hex = std::format!("{:x}", name);
+
+        let code = name.short_code(6);
+        assert_eq!(code.len(), 11);
+        assert_eq!(&code[..7], std::format!("{}-", &hex[..6]));
+        assert!(name.matches_short_code(&code));
+        assert!(name.matches_short_code(&code.to_uppercase()));
+        assert!(name.matches_short_code(&name.short_code(0)));
+        assert_eq!(name.short_code(100), name.short_code(64));
+        assert!(name.matches_short_code(&name.short_code(64)));
+
+        // A misread digit in either part is caught.
+        let mut other = name;
+        other.0[0] ^= 0x10;
+        assert!(!other.matches_short_code(&code));
+        assert!(!name.matches_short_code(&other.short_code(6)));
+        let mut wrong_checksum = code.clone().into_bytes();
+        wrong_checksum[10] = if wrong_checksum[10] == b'0' {
+            b'1'
+        } else {
+            b'0'
+        };
+        assert!(!name.matches_short_code(core::str::from_utf8(&wrong_checksum).unwrap()));
+
+        // Malformed codes
+        assert!(!name.matches_short_code(&hex[..6]));
+        assert!(!name.matches_short_code(&code[..10]));
+        assert!(!name.matches_short_code(&std::format!("{}0", code)));
+        assert!(!name.matches_short_code(&std::format!("{}0-{}", hex, &code[7..])));
+        assert!(!name.matches_short_code(&std::format!("{}-+{}", &hex[..6], &code[8..])));
+    }
+
+    #[test]
+    fn prefix_of() {
+        let name = xor_name!(0b1010_0101, 0xc3);
+        assert_eq!(name.prefix_of(0), Prefix::default());
+        assert_eq!(name.prefix_of(5), "10100".parse().unwrap());
+        assert_eq!(name.prefix_of(12).name(), xor_name!(0b1010_0101, 0xc0));
+        assert_eq!(name.prefix_of(300).bit_count(), 256);
+        assert!((0..=256).all(|bit_count| name.prefix_of(bit_count).matches(&name)));
+    }
+
+    #[test]
+    fn fold() {
+        assert_eq!(XorName::ZERO.fold_to_u64(), 0);
+        assert_eq!(XorName([0xFF; XOR_NAME_LEN]).fold_to_u64(), 0);
+        assert_eq!(xor_name!(0x12).fold_to_u64(), 0x12 << 56);
+        assert_eq!(xor_name!(0x12).fold_to_u32(), 0x12 << 24);
+
+        let mut last = XorName::ZERO;
+        last[XOR_NAME_LEN - 1] = 0xAB;
+        assert_eq!(last.fold_to_u64(), 0xAB);
+        assert_eq!(last.fold_to_u32(), 0xAB);
+
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let bytes = name.chunks(8).fold([0; 8], |mut folded: [u8; 8], chunk| {
+            folded.iter_mut().zip(chunk).for_each(|(f, c)| *f ^= c);
+            folded
+        });
+        assert_eq!(name.fold_to_u64(), u64::from_be_bytes(bytes));
+
+        // Names differing only in the last bit still fold to different values.
+        assert_ne!(name.fold_to_u32(), name.with_flipped_bit(255).fold_to_u32());
+    }
+
+    #[test]
+    fn bit_slice() {
+        let name = xor_name!(0b1010_0101, 0xc3, 0xff);
+        assert_eq!(name.bit_slice(0..8), 0b1010_0101);
+        assert_eq!(name.bit_slice(2..6), 0b1001);
+        assert_eq!(name.bit_slice(4..12), 0b0101_1100);
+        assert_eq!(name.bit_slice(0..1), 1);
+        assert_eq!(name.bit_slice(1..1), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = name.bit_slice(8..4);
+        assert_eq!(reversed, 0);
+
+        // Only the first 64 bits of longer ranges count.
+        assert_eq!(name.bit_slice(0..100), name.bit_slice(0..64));
+        assert_eq!(name.bit_slice(0..64), 0xa5c3_ff00_0000_0000);
+
+        // Bits past the end read as zeroes.
+        let ones = XorName([0xff; XOR_NAME_LEN]);
+        assert_eq!(ones.bit_slice(250..256), 0b11_1111);
+        assert_eq!(ones.bit_slice(250..260), 0b11_1111_0000);
+        assert_eq!(ones.bit_slice(256..300), 0);
+        assert_eq!(ones.bit_slice(usize::MAX - 1..usize::MAX), 0);
+
+        // Compare against a bit by bit reference.
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        for start in 0..8 * XOR_NAME_LEN {
+            for end in start..(start + 65).min(8 * XOR_NAME_LEN + 1) {
+                let expected = (start..end).fold(0, |acc, i| (acc << 1) | name.bit(i as u8) as u64);
+                assert_eq!(name.bit_slice(start..end), expected, "{}..{}", start, end);
+            }
+        }
+    }
+
     #[test]
     fn set_remaining() {
         assert_eq!(
@@ -627,6 +1260,84 @@ mod tests {
                 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255
             )
         );
+
+        // Bit counts at and beyond the end of the name.
+        let name = XorName([0xff; XOR_NAME_LEN]);
+        assert_eq!(name.set_remaining(255, false)[XOR_NAME_LEN - 1], 0xfe);
+        assert_eq!(name.set_remaining(256, false), name);
+        assert_eq!(name.set_remaining(300, false), name);
+    }
+
+    #[test]
+    fn checked_add() {
+        assert_eq!(from_u64(3).checked_add(&from_u64(4)), Some(from_u64(7)));
+        assert_eq!(
+            from_u64(u64::MAX).checked_add(&from_u64(1)),
+            Some(xor_name!(
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1
+            ))
+        );
+        let max = XorName([0xff; XOR_NAME_LEN]);
+        assert_eq!(max.checked_add(&XorName::ZERO), Some(max));
+        assert_eq!(max.checked_add(&from_u64(1)), None);
+        assert_eq!(xor_name!(0x80).checked_add(&xor_name!(0x80)), None);
+    }
+
+    #[test]
+    fn wrapping_sub() {
+        assert_eq!(from_u64(7).wrapping_sub(&from_u64(4)), from_u64(3));
+        assert_eq!(
+            xor_name!(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1)
+                .wrapping_sub(&from_u64(1)),
+            from_u64(u64::MAX)
+        );
+        let max = XorName([0xff; XOR_NAME_LEN]);
+        assert_eq!(max.wrapping_sub(&max), XorName::ZERO);
+        assert_eq!(XorName::ZERO.wrapping_sub(&from_u64(1)), max);
+    }
+
+    #[test]
+    fn div_rem() {
+        assert_eq!(
+            from_u64(23).div_rem(&from_u64(5)),
+            (from_u64(4), from_u64(3))
+        );
+        assert_eq!(
+            from_u64(3).div_rem(&from_u64(5)),
+            (XorName::ZERO, from_u64(3))
+        );
+        let max = XorName([0xff; XOR_NAME_LEN]);
+        assert_eq!(max.div_rem(&max), (from_u64(1), XorName::ZERO));
+        assert_eq!(max.div_rem(&from_u64(1)), (max, XorName::ZERO));
+        // Divisors above 2^255, where the shifted remainder needs 257 bits.
+        assert_eq!(
+            max.div_rem(&xor_name!(0x80)),
+            (from_u64(1), !xor_name!(0x80))
+        );
+        assert_eq!(
+            max.div_rem(&xor_name!(0x40)),
+            (from_u64(3), !xor_name!(0xc0))
+        );
+
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let (dividend, divisor) = (rng.gen::<u64>(), rng.gen_range(1..=u64::MAX));
+            assert_eq!(
+                from_u64(dividend).div_rem(&from_u64(divisor)),
+                (from_u64(dividend / divisor), from_u64(dividend % divisor))
+            );
+        }
+    }
+
+    #[test]
+    fn successor() {
+        assert_eq!(from_u64(0).successor(), Some(from_u64(1)));
+        assert_eq!(from_u64(0x01ff).successor(), Some(from_u64(0x0200)));
+        assert_eq!(
+            xor_name!(0x12, 0xff).set_remaining(8, true).successor(),
+            Some(xor_name!(0x13))
+        );
+        assert_eq!(XorName([0xff; XOR_NAME_LEN]).successor(), None);
     }
 
     #[test]
@@ -652,6 +1363,146 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compare_and_borrow_raw_bytes() {
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let other: XorName = rng.gen();
+
+        assert_eq!(name, name.0);
+        assert_eq!(name.0, name);
+        assert_ne!(name, other.0);
+        assert_eq!(name, &name.0[..]);
+        assert_ne!(name, &name.0[1..]);
+
+        let mut map = std::collections::HashMap::new();
+        let _ = map.insert(name, 1);
+        assert_eq!(map.get(&name.0), Some(&1));
+        assert_eq!(map.get(&other.0), None);
+
+        let set: std::collections::BTreeSet<_> = vec![name, other].into_iter().collect();
+        assert!(set.contains(&other.0));
+    }
+
+    #[test]
+    fn mutable_access() {
+        let mut rng = SmallRng::from_entropy();
+        let mut name = XorName::ZERO;
+        rng.fill(&mut name[..]);
+        assert!(!name.is_zero());
+
+        name[0] = 0xAB;
+        name.as_mut()[1] = 0xCD;
+        name[2..].copy_from_slice(&[0; XOR_NAME_LEN - 2]);
+        assert_eq!(name, xor_name!(0xAB, 0xCD));
+
+        let mut sha3 = Sha3::v256();
+        sha3.update(b"content");
+        sha3.finalize(&mut name);
+        assert_eq!(name, XorName::from_content(b"content"));
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+
+        assert_eq!(XorName::try_from(&name[..]), Ok(name));
+        assert_eq!(
+            XorName::try_from(&name[1..]),
+            Err(Error::InvalidLength {
+                expected: XOR_NAME_LEN,
+                actual: XOR_NAME_LEN - 1
+            })
+        );
+        assert_eq!(
+            &format!(32, "{}", XorName::try_from(&[0u8; 33][..]).unwrap_err()),
+            "expected 32 bytes, got 33"
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+
+        let lower = std::format!("{:x}", name);
+        let upper = std::format!("{:X}", name);
+        assert_eq!(lower.parse(), Ok(name));
+        assert_eq!(XorName::try_from(upper.as_str()), Ok(name));
+        assert_eq!(XorName::try_from(upper), Ok(name));
+
+        assert_eq!(lower[1..].parse::<XorName>(), Err(Error::InvalidHex));
+        assert_eq!(
+            std::format!("{}0", lower).parse::<XorName>(),
+            Err(Error::InvalidHex)
+        );
+        assert_eq!(
+            std::format!("g{}", &lower[1..]).parse::<XorName>(),
+            Err(Error::InvalidHex)
+        );
+        // Multi-byte characters are rejected rather than split.
+        assert_eq!(
+            std::format!("\u{e9}{}", &lower[2..]).parse::<XorName>(),
+            Err(Error::InvalidHex)
+        );
+        assert_eq!(
+            &format!(32, "{}", "".parse::<XorName>().unwrap_err()),
+            "expected 64 hex digits"
+        );
+    }
+
+    #[test]
+    fn layout() {
+        use core::ptr;
+
+        assert_eq!(size_of::<XorName>(), size_of::<[u8; XOR_NAME_LEN]>());
+        assert_eq!(align_of::<XorName>(), align_of::<[u8; XOR_NAME_LEN]>());
+        // No padding between names either.
+        assert_eq!(size_of::<[XorName; 3]>(), 3 * XOR_NAME_LEN);
+
+        let name = xor_name!(1, 2, 3);
+        assert_eq!(ptr::addr_of!(name).cast::<u8>(), name.0.as_ptr());
+    }
+
+    #[test]
+    fn generate_matching() {
+        let mut rng = SmallRng::from_entropy();
+        for &len in &[0, 5, 100, 256] {
+            let prefix = Prefix::new(len, rng.gen());
+            assert!(prefix.matches(&XorName::generate_matching(&prefix, &mut rng)));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn generate_where() {
+        let candidate = |i: u64| XorName::from_content(&i.to_be_bytes());
+        let wanted = Prefix::new(6, candidate(1000).with_flipped_bit(0));
+        let expected = (0..5000)
+            .map(|i| (i, candidate(i)))
+            .find(|(_, name)| wanted.matches(name));
+
+        assert_eq!(
+            XorName::generate_where(candidate, |name| wanted.matches(name), 5000),
+            expected
+        );
+        assert_eq!(
+            XorName::generate_where(candidate, |name| *name == candidate(1000), 1000),
+            None
+        );
+        assert_eq!(XorName::generate_where(candidate, |_| true, 0), None);
+    }
+
+    #[test]
+    fn xor_name_macro_in_const() {
+        const EMPTY: XorName = xor_name!();
+        const NAME: XorName = xor_name!(0xAA, 0xBB, 0xCC,);
+
+        assert_eq!(EMPTY, XorName::default());
+        assert_eq!(&NAME[..4], &[0xAA, 0xBB, 0xCC, 0x00]);
+    }
+
     #[test]
     fn conversion_from_u64() {
         assert_eq!(
@@ -0,0 +1,108 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Parallel versions of operations over large sets of names, using `rayon`.
+//!
+//! They give the same results as their sequential counterparts, and only pay off for inputs of
+//! many thousands of names.
+//!
+//! ```
+//! use rand::{rngs::SmallRng, Rng, SeedableRng};
+//! use xor_name::{par, prefix, XorName};
+//!
+//! let mut rng = SmallRng::seed_from_u64(0);
+//! let names: Vec<XorName> = (0..100_000).map(|_| rng.gen()).collect();
+//! let target: XorName = rng.gen();
+//!
+//! let closest = par::k_closest(&names, &target, 5);
+//! let mut sorted = names.clone();
+//! par::sort_by_distance(&mut sorted, &target);
+//! assert_eq!(closest, sorted[..5]);
+//!
+//! let (matching, _) = par::partition(&prefix!("01"), &names);
+//! assert!(matching.iter().all(|name| prefix!("01").matches(name)));
+//! ```
+
+use crate::{DistanceFrom, Prefix, XorDistance, XorName};
+use rayon::prelude::*;
+use std::collections::BinaryHeap;
+
+/// Sorts `names` by their distance from `target`, closest first.
+pub fn sort_by_distance(names: &mut [XorName], target: &XorName) {
+    names.par_sort_unstable_by(|lhs, rhs| target.cmp_distance(lhs, rhs));
+}
+
+/// Returns the `k` names closest to `target`, closest first.
+///
+/// Each thread keeps the `k` closest names of its share of the input, and these are merged at
+/// the end, so this takes `O(n log k)` work instead of sorting all names.
+pub fn k_closest(names: &[XorName], target: &XorName, k: usize) -> Vec<XorName> {
+    if k == 0 {
+        return Vec::new();
+    }
+    // Max-heaps of distances, so the farthest of the `k` closest is replaced first.
+    let keep = |mut heap: BinaryHeap<XorDistance>, distance: XorDistance| {
+        if heap.len() < k {
+            heap.push(distance);
+        } else if heap.peek().is_some_and(|farthest| distance < *farthest) {
+            let _ = heap.pop();
+            heap.push(distance);
+        }
+        heap
+    };
+    let closest = names
+        .par_iter()
+        .fold(BinaryHeap::new, |heap, name| {
+            keep(heap, target.distance(name))
+        })
+        .reduce(BinaryHeap::new, |lhs, rhs| rhs.into_iter().fold(lhs, keep));
+
+    closest
+        .into_sorted_vec()
+        .iter()
+        .map(|distance| DistanceFrom(target).name(distance))
+        .collect()
+}
+
+/// Splits `names` into those matching `prefix` and those that don't, keeping their order, like
+/// [`Prefix::partition`].
+pub fn partition<'a>(
+    prefix: &Prefix,
+    names: &'a [XorName],
+) -> (Vec<&'a XorName>, Vec<&'a XorName>) {
+    let matches = prefix.matcher();
+    names.par_iter().partition(|name| matches(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn matches_sequential() {
+        let mut rng = SmallRng::from_entropy();
+        let mut names: Vec<XorName> = (0..10_000).map(|_| rng.gen()).collect();
+        names.extend_from_within(..100);
+        let target: XorName = rng.gen();
+
+        let mut sorted = names.clone();
+        sorted.sort_by(|lhs, rhs| target.cmp_distance(lhs, rhs));
+        let mut par_sorted = names.clone();
+        sort_by_distance(&mut par_sorted, &target);
+        assert_eq!(par_sorted, sorted);
+
+        for &k in &[0, 1, 20, names.len(), names.len() + 1] {
+            assert_eq!(k_closest(&names, &target, k), sorted[..k.min(sorted.len())]);
+        }
+
+        let prefix = Prefix::new(3, rng.gen());
+        assert_eq!(partition(&prefix, &names), prefix.partition(&names));
+    }
+}
@@ -7,11 +7,13 @@
 // specific language governing permissions and limitations relating to use of the SAFE Network
 // Software.
 
-use crate::{XorName, XOR_NAME_LEN};
+use crate::{Error, XorDistance, XorName, WORD_COUNT, XOR_NAME_LEN};
 use core::{
     cmp::{self, Ordering},
+    convert::TryFrom,
     fmt::{Binary, Debug, Display, Formatter, Result as FmtResult},
     hash::{Hash, Hasher},
+    iter::FusedIterator,
     ops::RangeInclusive,
     str::FromStr,
 };
@@ -19,9 +21,11 @@ use core::{
 /// A section prefix, i.e. a sequence of bits specifying the part of the network's name space
 /// consisting of all names that start with this sequence.
 #[derive(Clone, Copy, Default, Eq)]
+#[cfg_attr(not(feature = "serialize-hex"), derive(serde::Serialize))]
 #[cfg_attr(
-    not(feature = "serialize-hex"),
-    derive(serde::Serialize, serde::Deserialize)
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary)
 )]
 pub struct Prefix {
     pub(crate) bit_count: u16,
@@ -32,9 +36,40 @@ impl Prefix {
     /// Creates a new `Prefix` with the first `bit_count` bits of `name`. Insignificant bits are all
     /// set to 0.
     pub fn new(bit_count: usize, name: XorName) -> Self {
+        let bit_count = bit_count.min(8 * XOR_NAME_LEN);
         Prefix {
-            bit_count: bit_count.min(8 * XOR_NAME_LEN) as u16,
-            name: name.set_remaining(bit_count as u8, false),
+            bit_count: bit_count as u16,
+            name: name.set_remaining(bit_count, false),
+        }
+    }
+
+    // Parses a prefix in const context, panicking on invalid input. Used by `prefix!`, so that
+    // invalid literals fail to compile.
+    #[doc(hidden)]
+    pub const fn __parse_const(bits: &str) -> Self {
+        let bits = bits.as_bytes();
+        let mut name = [0; XOR_NAME_LEN];
+        let mut bit_count = 0;
+        let mut i = 0;
+        while i < bits.len() {
+            match bits[i] {
+                b'_' => {}
+                b'0' | b'1' => {
+                    if bit_count == 8 * XOR_NAME_LEN {
+                        panic!("prefix! takes at most 256 bits");
+                    }
+                    if bits[i] == b'1' {
+                        name[bit_count / 8] |= 1 << (7 - bit_count % 8);
+                    }
+                    bit_count += 1;
+                }
+                _ => panic!("prefix! only accepts `0`, `1` and `_`"),
+            }
+            i += 1;
+        }
+        Prefix {
+            bit_count: bit_count as u16,
+            name: XorName(name),
         }
     }
 
@@ -55,6 +90,34 @@ impl Prefix {
         self
     }
 
+    /// Returns `self` with all of `bits` appended, in order, like repeated calls to `pushed`. Bits
+    /// beyond the maximum length for this type are ignored.
+    pub fn pushed_bits<I>(mut self, bits: I) -> Self
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        for bit in bits {
+            if self.bit_count() == 8 * XOR_NAME_LEN {
+                break;
+            }
+            self = self.pushed(bit);
+        }
+        self
+    }
+
+    /// Returns `self` followed by the bits of `suffix`. Bits beyond the maximum length for this
+    /// type are ignored, as in `pushed_bits`.
+    ///
+    /// ```
+    /// use xor_name::prefix;
+    ///
+    /// assert_eq!(prefix!("10").append(&prefix!("011")), prefix!("10011"));
+    /// ```
+    pub fn append(&self, suffix: &Self) -> Self {
+        let suffix_name = suffix.name;
+        self.pushed_bits((0..suffix.bit_count()).map(|i| suffix_name.bit(i as u8)))
+    }
+
     /// Returns a prefix copying the first `bitcount() - 1` bits from `self`,
     /// or `self` if it is already empty.
     pub fn popped(mut self) -> Self {
@@ -67,6 +130,7 @@ impl Prefix {
     }
 
     /// Returns the number of bits in the prefix.
+    #[inline]
     pub fn bit_count(&self) -> usize {
         self.bit_count as usize
     }
@@ -112,6 +176,49 @@ impl Prefix {
         self.name.common_prefix(name) >= self.bit_count()
     }
 
+    /// Splits `names` into those matching this prefix and those that don't, keeping their order.
+    ///
+    /// ```
+    /// use xor_name::{prefix, xor_name};
+    ///
+    /// let names = [xor_name!(0b0100_0000), xor_name!(0b1000_0000), xor_name!(0b0111_0000)];
+    /// let (matching, other) = prefix!("01").partition(&names);
+    /// assert_eq!(matching, [&names[0], &names[2]]);
+    /// assert_eq!(other, [&names[1]]);
+    /// ```
+    pub fn partition<'a>(&self, names: &'a [XorName]) -> (Vec<&'a XorName>, Vec<&'a XorName>) {
+        let matches = self.matcher();
+        names.iter().partition(|name| matches(name))
+    }
+
+    /// Returns a bitmask of the `names` matching this prefix: bit `i % 64` (counting from the
+    /// least significant one) of word `i / 64` is set if `names[i]` matches.
+    pub fn matches_many(&self, names: &[XorName]) -> Vec<u64> {
+        let matches = self.matcher();
+        names
+            .chunks(64)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, name)| matches(name))
+                    .fold(0, |mask, (i, _)| mask | 1 << i)
+            })
+            .collect()
+    }
+
+    // Returns a function equivalent to `matches`, which compares whole bytes and then the
+    // remaining bits under a mask computed once.
+    pub(crate) fn matcher(&self) -> impl Fn(&XorName) -> bool {
+        let (bytes, bits) = (self.bit_count() / 8, self.bit_count() % 8);
+        let name = self.name;
+        let mask = !(u8::MAX >> bits);
+        move |other: &XorName| {
+            name.0[..bytes] == other.0[..bytes]
+                && (bits == 0 || (name.0[bytes] ^ other.0[bytes]) & mask == 0)
+        }
+    }
+
     /// Compares the distance of `self` and `other` to `target`. Returns `Less` if `self` is closer,
     /// `Greater` if `other` is closer, and compares the prefix directly if of equal distance
     /// (this is to make sorting deterministic).
@@ -140,7 +247,7 @@ impl Prefix {
     /// Returns the smallest name matching the prefix
     pub fn lower_bound(&self) -> XorName {
         if self.bit_count() < 8 * XOR_NAME_LEN {
-            self.name.set_remaining(self.bit_count() as u8, false)
+            self.name.set_remaining(self.bit_count(), false)
         } else {
             self.name
         }
@@ -149,7 +256,7 @@ impl Prefix {
     /// Returns the largest name matching the prefix
     pub fn upper_bound(&self) -> XorName {
         if self.bit_count() < 8 * XOR_NAME_LEN {
-            self.name.set_remaining(self.bit_count() as u8, true)
+            self.name.set_remaining(self.bit_count(), true)
         } else {
             self.name
         }
@@ -160,36 +267,83 @@ impl Prefix {
         RangeInclusive::new(self.lower_bound(), self.upper_bound())
     }
 
+    /// Returns whether every name in `range` matches the prefix. This is true for an empty range.
+    pub fn covers(&self, range: &RangeInclusive<XorName>) -> bool {
+        range.is_empty() || (self.matches(range.start()) && self.matches(range.end()))
+    }
+
+    /// Returns whether at least one name in `range` matches the prefix.
+    pub fn intersects(&self, range: &RangeInclusive<XorName>) -> bool {
+        self.intersection(range).is_some()
+    }
+
+    /// Returns the names in `range` that match the prefix, or `None` if there are none.
+    pub fn intersection(&self, range: &RangeInclusive<XorName>) -> Option<RangeInclusive<XorName>> {
+        let start = self.lower_bound().max(*range.start());
+        let end = self.upper_bound().min(*range.end());
+        if start <= end {
+            Some(start..=end)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the fewest disjoint prefixes covering exactly the names that match `self` but not
+    /// `other`, shortest first.
+    ///
+    /// That is `self` itself if the two are incompatible, nothing if `other` is `self` or one of
+    /// its ancestors, and otherwise the siblings of `other` and of its ancestors below `self`.
+    pub fn subtract(&self, other: &Self) -> impl Iterator<Item = Prefix> {
+        let (whole, chain) = if self.is_compatible(other) {
+            (
+                None,
+                self.bit_count()..other.bit_count().max(self.bit_count()),
+            )
+        } else {
+            (Some(*self), 0..0)
+        };
+        let name = other.name;
+        whole.into_iter().chain(chain.map(move |bit_count| {
+            Prefix::new(bit_count + 1, name).with_flipped_bit(bit_count as u8)
+        }))
+    }
+
     /// Returns whether the namespace defined by `self` is covered by prefixes in the `prefixes`
     /// set
+    ///
+    /// Runs in `O(n log n)` for `n` prefixes, regardless of how much deeper than `self` they are.
     pub fn is_covered_by<'a, I>(&self, prefixes: I) -> bool
     where
-        I: IntoIterator<Item = &'a Self> + Clone,
+        I: IntoIterator<Item = &'a Self>,
     {
-        let max_prefix_len = prefixes
-            .clone()
-            .into_iter()
-            .map(Self::bit_count)
-            .max()
-            .unwrap_or(0);
-        self.is_covered_by_impl(prefixes, max_prefix_len)
-    }
+        let mut descendants = Vec::new();
+        for prefix in prefixes {
+            if prefix.is_compatible(self) {
+                if prefix.bit_count() <= self.bit_count() {
+                    return true;
+                }
+                descendants.push((prefix.lower_bound(), prefix.upper_bound()));
+            }
+        }
 
-    fn is_covered_by_impl<'a, I>(&self, prefixes: I, max_prefix_len: usize) -> bool
-    where
-        I: IntoIterator<Item = &'a Self> + Clone,
-    {
-        prefixes
-            .clone()
-            .into_iter()
-            .any(|x| x.is_compatible(self) && x.bit_count() <= self.bit_count())
-            || (self.bit_count() <= max_prefix_len
-                && self
-                    .pushed(false)
-                    .is_covered_by_impl(prefixes.clone(), max_prefix_len)
-                && self
-                    .pushed(true)
-                    .is_covered_by_impl(prefixes, max_prefix_len))
+        // Sweep over the descendants in name order, tracking the first name not yet known to be
+        // covered. Two descendants are either nested or disjoint, so any gap in between them
+        // means `self` isn't covered.
+        descendants.sort_unstable();
+        let mut uncovered = self.lower_bound();
+        for (lower, upper) in descendants {
+            if lower > uncovered {
+                return false;
+            }
+            if upper >= uncovered {
+                match upper.successor() {
+                    Some(next) => uncovered = next,
+                    // Covered up to the very last name.
+                    None => return true,
+                }
+            }
+        }
+        uncovered > self.upper_bound()
     }
 
     /// Returns the neighbouring prefix differing in the `i`-th bit
@@ -204,13 +358,106 @@ impl Prefix {
 
     /// Returns the given `name` with first bits replaced by `self`
     pub fn substituted_in(&self, mut name: XorName) -> XorName {
-        // TODO: is there a more efficient way of doing that?
-        for i in 0..self.bit_count() {
-            name = name.with_bit(i as u8, self.name.bit(i as u8));
+        for i in 0..WORD_COUNT {
+            // Mask of the significant bits of the prefix within this word.
+            let bits = self.bit_count().saturating_sub(64 * i).min(64) as u32;
+            let mask = u64::MAX.checked_shl(64 - bits).unwrap_or(0);
+            let word = (self.name.word(i) & mask) | (name.word(i) & !mask);
+            name.0[8 * i..8 * (i + 1)].copy_from_slice(&word.to_be_bytes());
         }
         name
     }
 
+    /// Returns `n` evenly spaced names matching this prefix, in ascending order: the first name
+    /// of each of `n` equally sized slices of the prefix's range, starting at `lower_bound`.
+    ///
+    /// The result only depends on the prefix and `n`, so can be used for reproducible probes. If
+    /// `n` is larger than the number of names in the prefix, some names are repeated.
+    pub fn sample_names(
+        &self,
+        n: usize,
+    ) -> impl DoubleEndedIterator<Item = XorName> + ExactSizeIterator + FusedIterator {
+        let prefix = *self;
+        (0..n).map(move |i| prefix.sample_name(i, n))
+    }
+
+    // Returns `lower_bound + floor(i * 2^r / n)` where `r` is the number of insignificant bits,
+    // computing the quotient bit by bit by long division.
+    fn sample_name(&self, i: usize, n: usize) -> XorName {
+        let mut name = self.lower_bound();
+        let mut remainder = i as u128;
+        for bit in self.bit_count()..8 * XOR_NAME_LEN {
+            remainder *= 2;
+            if remainder >= n as u128 {
+                remainder -= n as u128;
+                name = name.with_bit(bit as u8, true);
+            }
+        }
+        name
+    }
+
+    /// Returns the position of this prefix among all prefixes with the same bit count, in
+    /// ascending order, i. e. its bits read as a number. Returns `None` if the prefix has more
+    /// than 64 bits.
+    ///
+    /// This is the inverse of [`Prefix::from_index`].
+    pub fn to_index(&self) -> Option<u64> {
+        if self.bit_count() > 64 {
+            return None;
+        }
+        Some(self.name.bit_slice(0..self.bit_count()))
+    }
+
+    /// Returns the prefix with `bit_count` bits at the given position among all prefixes of that
+    /// length, i. e. the prefix whose bits read as a number are `index`. Returns `None` if
+    /// `bit_count` is more than 64, or `index` is not less than `2^bit_count`.
+    ///
+    /// ```
+    /// use xor_name::Prefix;
+    ///
+    /// let prefix = Prefix::from_index(4, 0b0110).unwrap();
+    /// assert_eq!(prefix.to_string(), "0110");
+    /// assert_eq!(prefix.to_index(), Some(0b0110));
+    /// ```
+    pub fn from_index(bit_count: usize, index: u64) -> Option<Self> {
+        if bit_count > 64 || index.checked_shr(bit_count as u32).unwrap_or(0) != 0 {
+            return None;
+        }
+        let mut name = XorName::ZERO;
+        let word = index.checked_shl(64 - bit_count as u32).unwrap_or(0);
+        name.0[..8].copy_from_slice(&word.to_be_bytes());
+        Some(Self::new(bit_count, name))
+    }
+
+    /// Returns the XOR distance from `name` to the closest name matching this prefix. That is
+    /// zero if the prefix matches `name`, and otherwise only depends on the significant bits.
+    pub fn min_distance_to(&self, name: &XorName) -> XorDistance {
+        // Taking the remaining bits from `name` itself makes them cancel out.
+        name.distance(&self.substituted_in(*name))
+    }
+
+    /// Returns the smallest XOR distance between a name matching `self` and one matching
+    /// `other`. That is zero if the prefixes are compatible, and otherwise only depends on the
+    /// bits of the shorter one.
+    pub fn distance(&self, other: &Self) -> XorDistance {
+        let (shorter, longer) = if self.bit_count <= other.bit_count {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        shorter.min_distance_to(&longer.name)
+    }
+
+    /// Compares the distances of `self` and `other` to the `target` prefix, as given by
+    /// [`Prefix::distance`]. Returns `Less` if `self` is closer. Prefixes of equal distance, such
+    /// as all those compatible with `target`, are ordered by [`Prefix::cmp_breadth_first`], so
+    /// sorting is deterministic.
+    pub fn cmp_distance_to_prefix(&self, other: &Self, target: &Self) -> Ordering {
+        self.distance(target)
+            .cmp(&other.distance(target))
+            .then_with(|| self.cmp_breadth_first(other))
+    }
+
     /// Returns the same prefix, with the last bit flipped, or unchanged, if empty.
     pub fn sibling(&self) -> Self {
         if self.bit_count() > 0 && self.bit_count() < 8 * XOR_NAME_LEN {
@@ -224,24 +471,172 @@ impl Prefix {
     ///
     /// # Panics
     ///
-    /// Panics if `bit_count` is not less than the bit count of this prefix.
+    /// Panics if `bit_count` is not less than the bit count of this prefix. See
+    /// [`Prefix::try_ancestor`] for a non-panicking version.
     pub fn ancestor(&self, bit_count: u8) -> Self {
         assert!((bit_count as usize) < self.bit_count());
         Self::new(bit_count as usize, self.name)
     }
 
+    /// Returns the ancestor of this prefix that has the given bit count, or
+    /// [`Error::NotAnAncestor`] if `bit_count` is not less than the bit count of this prefix.
+    pub fn try_ancestor(&self, bit_count: usize) -> Result<Self, Error> {
+        if bit_count >= self.bit_count() {
+            return Err(Error::NotAnAncestor(bit_count));
+        }
+        Ok(Self::new(bit_count, self.name))
+    }
+
     /// Returns an iterator that yields all ancestors of this prefix.
     pub fn ancestors(&self) -> Ancestors {
         Ancestors {
             target: *self,
             current_len: 0,
+            end_len: self.bit_count(),
         }
     }
+
+    /// Returns the siblings of this prefix's ancestors and of the prefix itself: for each depth
+    /// `k` from `1` to the bit count, the prefix of `k` bits that agrees with `self` on the first
+    /// `k - 1` bits and differs in the last one. The shortest, and thus largest, comes first.
+    ///
+    /// These are the Kademlia bucket prefixes relative to `self`: the `k`-th one holds exactly
+    /// the names whose common prefix with `self` has `k - 1` bits. They are disjoint, and together
+    /// with `self` they cover the whole name space.
+    ///
+    /// ```
+    /// use xor_name::prefix;
+    ///
+    /// let chain: Vec<_> = prefix!("101").sibling_chain().map(|p| p.to_string()).collect();
+    /// assert_eq!(chain, ["0", "11", "100"]);
+    /// ```
+    pub fn sibling_chain(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + FusedIterator {
+        let name = self.name;
+        (0..self.bit_count()).map(move |i| Self::new(i + 1, name).with_flipped_bit(i as u8))
+    }
+
+    /// Returns an iterator over this prefix and all its descendants with at most `max_depth`
+    /// bits, in breadth-first order: shorter prefixes first, and prefixes of the same length in
+    /// ascending order. Yields nothing if `max_depth` is less than the bit count of `self`.
+    ///
+    /// The iterator only stores the current prefix, however many prefixes it yields.
+    ///
+    /// ```
+    /// use xor_name::Prefix;
+    ///
+    /// let prefix: Prefix = "1".parse().unwrap();
+    /// let subtree: Vec<_> = prefix.subtree(3).map(|p| p.to_string()).collect();
+    /// assert_eq!(subtree, ["1", "10", "11", "100", "101", "110", "111"]);
+    /// ```
+    pub fn subtree(&self, max_depth: usize) -> Subtree {
+        let max_depth = max_depth.min(8 * XOR_NAME_LEN);
+        Subtree {
+            root: *self,
+            next: if max_depth >= self.bit_count() {
+                Some(*self)
+            } else {
+                None
+            },
+            max_depth,
+        }
+    }
+
+    /// Returns an iterator over the names matching this prefix, from `lower_bound` up to at most
+    /// `upper_bound`, each `step` (read as a 256-bit number) greater than the previous one.
+    ///
+    /// A zero `step` yields only the lower bound.
+    ///
+    /// ```
+    /// use xor_name::{xor_name, Prefix};
+    ///
+    /// // Every 2^252nd name, i.e. the first name of each 4 bit prefix.
+    /// let step = xor_name!(0b0001_0000);
+    /// let names: Vec<_> = Prefix::default().names(step).collect();
+    /// assert_eq!(names.len(), 16);
+    /// assert_eq!(names[3], xor_name!(0b0011_0000));
+    /// ```
+    pub fn names(&self, step: XorName) -> Names {
+        Names::new(self.lower_bound()..=self.upper_bound(), step)
+    }
+
+    /// Encodes the prefix as its bit count (two bytes, big endian) followed by only those bytes
+    /// of the name that contain significant bits.
+    #[cfg(any(
+        feature = "sqlx",
+        feature = "diesel-postgres",
+        feature = "diesel-mysql",
+        feature = "diesel-sqlite",
+        all(test, feature = "diesel")
+    ))]
+    pub(crate) fn to_compact_bytes(self) -> Vec<u8> {
+        let byte_count = self.bit_count().div_ceil(8);
+        let mut bytes = Vec::with_capacity(2 + byte_count);
+        bytes.extend_from_slice(&self.bit_count.to_be_bytes());
+        bytes.extend_from_slice(&self.name[..byte_count]);
+        bytes
+    }
+
+    /// Decodes a prefix produced by `to_compact_bytes`. Returns `None` if the bit count exceeds
+    /// 256 or doesn't match the number of bytes that follow it.
+    #[cfg(any(feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_compact_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 2 {
+            return None;
+        }
+        let bit_count = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let significant = &bytes[2..];
+        if bit_count as usize > 8 * XOR_NAME_LEN
+            || significant.len() != (bit_count as usize).div_ceil(8)
+        {
+            return None;
+        }
+
+        let mut name = XorName::default();
+        name.0[..significant.len()].copy_from_slice(significant);
+        if bit_count % 8 != 0 {
+            name.0[significant.len() - 1] &= !(0xff >> (bit_count % 8));
+        }
+
+        Some(Prefix { bit_count, name })
+    }
+}
+
+// The fields of a `Prefix` as they appear in the compact serialized form, before validation.
+#[derive(serde::Deserialize)]
+#[serde(rename = "Prefix")]
+pub(crate) struct PrefixFields {
+    bit_count: u16,
+    name: XorName,
+}
+
+impl PrefixFields {
+    // Rejects bit counts above 256 and clears any insignificant bits of the name, so that peers
+    // can't produce prefixes that `Prefix::new` never would.
+    pub(crate) fn validate(self) -> Result<Prefix, Error> {
+        if self.bit_count as usize > 8 * XOR_NAME_LEN {
+            return Err(Error::BitIndexOutOfRange(self.bit_count as usize));
+        }
+        Ok(Prefix::new(self.bit_count as usize, self.name))
+    }
+}
+
+#[cfg(not(feature = "serialize-hex"))]
+impl<'de> serde::Deserialize<'de> for Prefix {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PrefixFields::deserialize(deserializer)?
+            .validate()
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 impl PartialEq for Prefix {
     fn eq(&self, other: &Self) -> bool {
-        self.is_compatible(other) && self.bit_count == other.bit_count
+        // Only the significant bits are compared, so a single scan of the names is needed, and
+        // none at all if the lengths differ.
+        self.bit_count == other.bit_count
+            && self.name.common_prefix(&other.name) >= self.bit_count()
     }
 }
 
@@ -253,12 +648,15 @@ impl PartialOrd for Prefix {
 
 impl Ord for Prefix {
     fn cmp(&self, other: &Self) -> Ordering {
-        if self == other {
-            Ordering::Equal
-        } else if self.is_compatible(other) {
-            self.bit_count().cmp(&other.bit_count())
+        let common = self.name.common_prefix(&other.name);
+        if common >= cmp::min(self.bit_count(), other.bit_count()) {
+            // One is a prefix of the other (or they are equal): the shorter one comes first.
+            self.bit_count.cmp(&other.bit_count)
+        } else if self.name.bit(common as u8) {
+            // The first differing bit is significant in both, and decides the order.
+            Ordering::Greater
         } else {
-            self.name.cmp(&other.name)
+            Ordering::Less
         }
     }
 }
@@ -277,88 +675,396 @@ impl Binary for Prefix {
     }
 }
 
+/// The default format shows only the bits, e.g. `Prefix(010)`. The alternate format (`{:#?}`)
+/// also shows the bit count and the range of names matching the prefix.
 impl Debug for Prefix {
     fn fmt(&self, formatter: &mut Formatter) -> FmtResult {
+        if formatter.alternate() {
+            return formatter
+                .debug_struct("Prefix")
+                .field("bit_count", &self.bit_count)
+                .field("bits", &format_args!("\"{:b}\"", self))
+                .field("lower_bound", &format_args!("{:x}", self.lower_bound()))
+                .field("upper_bound", &format_args!("{:x}", self.upper_bound()))
+                .finish();
+        }
+
         write!(formatter, "Prefix({:b})", self)
     }
 }
 
 /// Format `Prefix` as bit string, e.g. `"010"` with a [`Prefix::bit_count`] of `3`.
+///
+/// The alternate format (`{:#}`) separates each group of 8 bits with a `_`, e.g.
+/// `"10110010_0111"`. Both forms are accepted by `from_str`.
 impl Display for Prefix {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        // Use `Binary` impl from `XorName` with restricted width
-        write!(f, "{:width$b}", self.name, width = self.bit_count as usize)
-    }
-}
-
-#[derive(Debug)]
-pub enum FromStrError {
-    InvalidChar(char),
-    TooLong(usize),
-}
-
-impl Display for FromStrError {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        match self {
-            FromStrError::InvalidChar(c) => {
-                write!(f, "expected `0` or `1`, but encountered `{}`", c)
-            }
-            FromStrError::TooLong(l) => {
+        if f.alternate() {
+            for start in (0..self.bit_count()).step_by(8) {
+                let end = cmp::min(start + 8, self.bit_count());
+                if start > 0 {
+                    write!(f, "_")?;
+                }
                 write!(
                     f,
-                    "max length exceeded {} with length of {l}",
-                    XOR_NAME_LEN * 8
-                )
+                    "{:0width$b}",
+                    self.name.bit_slice(start..end),
+                    width = end - start
+                )?;
             }
+            return Ok(());
         }
+
+        // Use `Binary` impl from `XorName` with restricted width
+        write!(f, "{:width$b}", self.name, width = self.bit_count as usize)
     }
 }
 
+/// Parses a string of `0`s and `1`s. Any `_` are ignored, so the alternate `Display` format can
+/// be parsed too.
 impl FromStr for Prefix {
-    type Err = FromStrError;
+    type Err = Error;
 
     fn from_str(bits: &str) -> Result<Self, Self::Err> {
-        if bits.len() > XOR_NAME_LEN * 8 {
-            return Err(FromStrError::TooLong(bits.len()));
-        }
         let mut name = [0; XOR_NAME_LEN];
-        for (i, bit) in bits.chars().enumerate() {
+        let mut bit_count = 0;
+        for bit in bits.chars().filter(|c| *c != '_') {
             if bit == '1' {
-                let byte = i / 8;
-                name[byte] |= 1 << (7 - (i % 8));
+                if bit_count < XOR_NAME_LEN * 8 {
+                    name[bit_count / 8] |= 1 << (7 - (bit_count % 8));
+                }
             } else if bit != '0' {
-                return Err(FromStrError::InvalidChar(bit));
+                return Err(Error::InvalidChar(bit));
             }
+            bit_count += 1;
+        }
+        if bit_count > XOR_NAME_LEN * 8 {
+            return Err(Error::TooLong(bit_count));
+        }
+        Ok(Self::new(bit_count, XorName(name)))
+    }
+}
+
+impl TryFrom<&str> for Prefix {
+    type Error = Error;
+
+    fn try_from(bits: &str) -> Result<Self, Self::Error> {
+        bits.parse()
+    }
+}
+
+impl TryFrom<String> for Prefix {
+    type Error = Error;
+
+    fn try_from(bits: String) -> Result<Self, Self::Error> {
+        bits.parse()
+    }
+}
+
+/// The full-length prefix matching only the given name.
+impl From<XorName> for Prefix {
+    fn from(name: XorName) -> Self {
+        Self::new(8 * XOR_NAME_LEN, name)
+    }
+}
+
+/// The only name matching a full-length prefix. Fails with [`Error::IncompletePrefix`] for
+/// shorter prefixes.
+impl TryFrom<Prefix> for XorName {
+    type Error = Error;
+
+    fn try_from(prefix: Prefix) -> Result<Self, Self::Error> {
+        if prefix.bit_count() < 8 * XOR_NAME_LEN {
+            return Err(Error::IncompletePrefix(prefix.bit_count()));
         }
-        Ok(Self::new(bits.len(), XorName(name)))
+        Ok(prefix.name)
+    }
+}
+
+/// A `Prefix` ordered breadth-first, by [`Prefix::cmp_breadth_first`], instead of depth-first.
+///
+/// Use it as the key of a `BTreeMap` or `BTreeSet` to iterate over prefixes level by level:
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use xor_name::{prefix, BreadthFirst};
+///
+/// let set: BTreeSet<_> = [prefix!("01"), prefix!("1"), prefix!("")]
+///     .iter()
+///     .copied()
+///     .map(BreadthFirst)
+///     .collect();
+/// let order: Vec<_> = set.iter().map(|key| key.0).collect();
+/// assert_eq!(order, [prefix!(""), prefix!("1"), prefix!("01")]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BreadthFirst(pub Prefix);
+
+impl PartialOrd for BreadthFirst {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BreadthFirst {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_breadth_first(&other.0)
+    }
+}
+
+impl From<Prefix> for BreadthFirst {
+    fn from(prefix: Prefix) -> Self {
+        Self(prefix)
     }
 }
 
 /// Iterator that yields the ancestors of the given prefix starting at the root prefix.
 /// Does not include the prefix itself.
+///
+/// Iterate it in reverse to start with the longest ancestor, the prefix's parent.
 pub struct Ancestors {
     target: Prefix,
     current_len: usize,
+    end_len: usize,
 }
 
 impl Iterator for Ancestors {
     type Item = Prefix;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current_len < self.target.bit_count() {
-            let output = self.target.ancestor(self.current_len as u8);
+        if self.current_len < self.end_len {
+            let output = Prefix::new(self.current_len, self.target.name);
             self.current_len += 1;
             Some(output)
         } else {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end_len - self.current_len;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Ancestors {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_len < self.end_len {
+            self.end_len -= 1;
+            Some(Prefix::new(self.end_len, self.target.name))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Ancestors {}
+
+impl FusedIterator for Ancestors {}
+
+/// Returns an iterator over all prefixes with at most `max_depth` bits, in breadth-first order:
+/// shorter prefixes first, and prefixes of the same length in ascending order.
+///
+/// This is the subtree of the empty prefix, see [`Prefix::subtree`].
+///
+/// ```
+/// use xor_name::all_prefixes;
+///
+/// let prefixes: Vec<_> = all_prefixes(2).map(|p| p.to_string()).collect();
+/// assert_eq!(prefixes, ["", "0", "1", "00", "01", "10", "11"]);
+/// ```
+pub fn all_prefixes(max_depth: usize) -> Subtree {
+    Prefix::default().subtree(max_depth)
+}
+
+/// Returns an iterator over `start` and all prefixes following it in the order of
+/// [`all_prefixes`], up to `max_depth` bits. Yields nothing if `start` has more bits than that.
+///
+/// This resumes an enumeration that was interrupted: to continue after the last prefix
+/// processed, skip the first one yielded.
+///
+/// ```
+/// use xor_name::{all_prefixes_from, prefix};
+///
+/// let last_done = prefix!("01");
+/// let rest: Vec<_> = all_prefixes_from(&last_done, 2).skip(1).map(|p| p.to_string()).collect();
+/// assert_eq!(rest, ["10", "11"]);
+/// ```
+pub fn all_prefixes_from(start: &Prefix, max_depth: usize) -> Subtree {
+    let max_depth = max_depth.min(8 * XOR_NAME_LEN);
+    Subtree {
+        root: Prefix::default(),
+        next: Some(*start).filter(|start| start.bit_count() <= max_depth),
+        max_depth,
+    }
+}
+
+/// Breadth-first iterator over a prefix and its descendants, created by [`Prefix::subtree`],
+/// [`all_prefixes`] and [`all_prefixes_from`].
+///
+/// Its size hint is exact unless the number of remaining prefixes overflows a `usize`.
+pub struct Subtree {
+    root: Prefix,
+    next: Option<Prefix>,
+    max_depth: usize,
+}
+
+impl Iterator for Subtree {
+    type Item = Prefix;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let depth = current.bit_count();
+
+        // The next prefix of the same length, if it's still a descendant of the root.
+        let next_sibling = if depth > self.root.bit_count() {
+            let step = XorName::ZERO.with_bit((depth - 1) as u8, true);
+            current
+                .name
+                .checked_add(&step)
+                .filter(|name| self.root.matches(name))
+                .map(|name| Prefix::new(depth, name))
+        } else {
+            None
+        };
+
+        // Otherwise the first prefix of the next level.
+        self.next = next_sibling.or_else(|| {
+            if depth < self.max_depth {
+                Some(Prefix::new(depth + 1, self.root.name))
+            } else {
+                None
+            }
+        });
+
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let next = match self.next {
+            Some(next) => next,
+            None => return (0, Some(0)),
+        };
+        let depth = next.bit_count();
+        let root_depth = self.root.bit_count();
+
+        // The prefixes of the current level from `next` onwards: one more than the complement of
+        // the bits below the root, which fits in a `usize` only if all but the last few are set.
+        let low = (depth - root_depth).min(usize::BITS as usize - 1);
+        let remaining = if (root_depth..depth - low).all(|i| next.name.bit(i as u8)) {
+            Some(!next.name.bit_slice(depth - low..depth) as usize & ((1 << low) - 1))
+        } else {
+            None
+        };
+
+        // Then all the prefixes of each deeper level.
+        let count = (depth + 1..=self.max_depth).fold(
+            remaining.and_then(|remaining| remaining.checked_add(1)),
+            |count, level| count?.checked_add(1usize.checked_shl((level - root_depth) as u32)?),
+        );
+        match count {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Subtree {}
+
+/// Iterator over evenly spaced names in a range, created by [`Prefix::names`] and
+/// [`XorName::names_between`].
+///
+/// Iterate it in reverse to start with the last name it reaches, the end of the range rounded
+/// down to a whole number of steps.
+pub struct Names {
+    next: Option<XorName>,
+    last: XorName,
+    step: XorName,
 }
 
+impl Names {
+    pub(crate) fn new(range: RangeInclusive<XorName>, step: XorName) -> Self {
+        let (start, last) = range.into_inner();
+        Self {
+            next: Some(start).filter(|start| *start <= last),
+            last,
+            step,
+        }
+    }
+
+    /// Returns the range of names still to be visited, or `None` if the iterator is exhausted.
+    ///
+    /// Passing it to [`XorName::names_between`] with the same step resumes the iteration.
+    pub fn remaining(&self) -> Option<RangeInclusive<XorName>> {
+        self.next.map(|next| next..=self.last)
+    }
+}
+
+impl Iterator for Names {
+    type Item = XorName;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = if self.step.is_zero() {
+            None
+        } else {
+            current
+                .checked_add(&self.step)
+                .filter(|next| *next <= self.last)
+        };
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let next = match self.next {
+            Some(next) => next,
+            None => return (0, Some(0)),
+        };
+        if self.step.is_zero() {
+            return (1, Some(1));
+        }
+        let (steps, _) = self.last.wrapping_sub(&next).div_rem(&self.step);
+        let count = if steps.0[..XOR_NAME_LEN - 8].iter().all(|byte| *byte == 0) {
+            usize::try_from(steps.word(WORD_COUNT - 1))
+                .ok()
+                .and_then(|steps| steps.checked_add(1))
+        } else {
+            None
+        };
+        match count {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Names {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = self.next?;
+        if self.step.is_zero() {
+            self.next = None;
+            return Some(next);
+        }
+
+        // The last name reached is a whole number of steps from `next`, at or before `last`.
+        let (_, overshoot) = self.last.wrapping_sub(&next).div_rem(&self.step);
+        let current = self.last.wrapping_sub(&overshoot);
+        if current == next {
+            self.next = None;
+        } else {
+            self.last = current.wrapping_sub(&self.step);
+        }
+        Some(current)
+    }
+}
+
+impl FusedIterator for Names {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+    use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+    use std::collections::BTreeSet;
 
     #[test]
     fn prefix() {
@@ -388,6 +1094,379 @@ mod tests {
         // Check we handle passing an excessive `bit_count` to `new()`.
         assert_eq!(Prefix::new(256, xor_name!(0)).bit_count(), 256);
         assert_eq!(Prefix::new(257, xor_name!(0)).bit_count(), 256);
+        let name = XorName([0xAA; XOR_NAME_LEN]);
+        assert_eq!(Prefix::new(256, name).name(), name);
+        assert_eq!(Prefix::new(257, name).name(), name);
+        assert_eq!(Prefix::new(usize::MAX, name).name(), name);
+        assert_eq!(Prefix::new(255, name).name()[XOR_NAME_LEN - 1], 0xAA);
+        assert_eq!(Prefix::new(250, name).name()[XOR_NAME_LEN - 1], 0x80);
+    }
+
+    #[test]
+    fn is_covered_by() {
+        let prefixes = |bits: &[&str]| bits.iter().map(|b| parse(b)).collect::<Vec<_>>();
+
+        assert!(!parse("").is_covered_by(&prefixes(&[])));
+        assert!(parse("").is_covered_by(&prefixes(&[""])));
+        assert!(parse("").is_covered_by(&prefixes(&["0", "1"])));
+        assert!(parse("").is_covered_by(&prefixes(&["1", "00", "01"])));
+        assert!(!parse("").is_covered_by(&prefixes(&["1", "00"])));
+        assert!(!parse("").is_covered_by(&prefixes(&["0", "11"])));
+
+        // ancestors cover their descendants
+        assert!(parse("0110").is_covered_by(&prefixes(&["01"])));
+        assert!(parse("0110").is_covered_by(&prefixes(&["0110"])));
+        assert!(!parse("0110").is_covered_by(&prefixes(&["0111", "1"])));
+
+        // nested and redundant descendants
+        assert!(parse("01").is_covered_by(&prefixes(&["010", "0100", "0101", "011", "0110"])));
+        assert!(!parse("01").is_covered_by(&prefixes(&["0100", "0101", "0110"])));
+
+        // deep descendants: the whole sibling chain of a 200 bit prefix, plus the prefix itself
+        let deep = Prefix::new(200, XorName([0x5a; XOR_NAME_LEN]));
+        let mut chain: Vec<_> = (1..=deep.bit_count())
+            .map(|len| Prefix::new(len, deep.name()).sibling())
+            .collect();
+        assert!(!parse("").is_covered_by(&chain));
+        chain.push(deep);
+        assert!(parse("").is_covered_by(&chain));
+        assert!(parse("0").is_covered_by(&chain));
+    }
+
+    #[test]
+    fn range_queries() {
+        let prefix = parse("01");
+        let (lower, upper) = (prefix.lower_bound(), prefix.upper_bound());
+        let before = parse("00").upper_bound();
+        let after = parse("10").lower_bound();
+
+        // ranges touching the prefix only at an inclusive edge
+        assert!(prefix.intersects(&(before..=lower)));
+        assert_eq!(prefix.intersection(&(before..=lower)), Some(lower..=lower));
+        assert!(!prefix.covers(&(before..=lower)));
+        assert!(prefix.intersects(&(upper..=after)));
+        assert_eq!(prefix.intersection(&(upper..=after)), Some(upper..=upper));
+        assert!(!prefix.covers(&(upper..=after)));
+
+        // ranges just outside the prefix
+        assert!(!prefix.intersects(&(XorName::ZERO..=before)));
+        assert_eq!(
+            prefix.intersection(&(after..=XorName([0xFF; XOR_NAME_LEN]))),
+            None
+        );
+
+        // the prefix's own range, and ranges inside and around it
+        assert!(prefix.covers(&prefix.range_inclusive()));
+        assert!(prefix.covers(&(lower..=lower)));
+        assert!(!prefix.covers(&(before..=after)));
+        assert_eq!(
+            prefix.intersection(&(before..=after)),
+            Some(prefix.range_inclusive())
+        );
+        assert!(parse("").covers(&(before..=after)));
+
+        // empty ranges
+        assert!(prefix.covers(&(after..=before)));
+        assert!(!prefix.intersects(&(upper..=lower)));
+
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let prefix = Prefix::new(rng.gen_range(0..8), rng.gen());
+            let (a, b): (XorName, XorName) = (rng.gen(), rng.gen());
+            let range = a.min(b)..=a.max(b);
+            let intersection = prefix.intersection(&range);
+            assert_eq!(
+                intersection.is_some(),
+                prefix.matches(&a) || prefix.matches(&b) || range.contains(&prefix.lower_bound())
+            );
+            if let Some(intersection) = intersection {
+                assert!(prefix.covers(&intersection));
+            }
+        }
+    }
+
+    #[test]
+    fn subtract() {
+        let subtract = |lhs: &str, rhs: &str| {
+            parse(lhs)
+                .subtract(&parse(rhs))
+                .map(|prefix| prefix.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(subtract("", ""), Vec::<String>::new());
+        assert_eq!(subtract("01", "0"), Vec::<String>::new());
+        assert_eq!(subtract("01", "1"), ["01"]);
+        assert_eq!(subtract("01", "00"), ["01"]);
+        assert_eq!(subtract("", "0"), ["1"]);
+        assert_eq!(subtract("0", "0110"), ["00", "010", "0111"]);
+
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let name: XorName = rng.gen();
+            let lhs = Prefix::new(rng.gen_range(0..=8 * XOR_NAME_LEN), name);
+            let rhs = if rng.gen() {
+                Prefix::new(rng.gen_range(0..=8 * XOR_NAME_LEN), name)
+            } else {
+                Prefix::new(rng.gen_range(0..=8), rng.gen())
+            };
+            let difference: Vec<_> = lhs.subtract(&rhs).collect();
+
+            for (i, prefix) in difference.iter().enumerate() {
+                assert!(prefix.is_compatible(&lhs) && prefix.bit_count() >= lhs.bit_count());
+                assert!(!prefix.is_compatible(&rhs));
+                assert!(difference[..i].iter().all(|p| !p.is_compatible(prefix)));
+            }
+            let mut cover = difference.clone();
+            cover.push(rhs);
+            assert!(lhs.is_covered_by(&cover));
+            if lhs.is_compatible(&rhs) {
+                assert_eq!(
+                    difference.len(),
+                    rhs.bit_count().saturating_sub(lhs.bit_count())
+                );
+            } else {
+                assert_eq!(difference, [lhs]);
+            }
+        }
+    }
+
+    #[test]
+    fn substituted_in() {
+        let name = XorName([0xAA; XOR_NAME_LEN]);
+        assert_eq!(parse("").substituted_in(name), name);
+        assert_eq!(parse("0").substituted_in(name)[0], 0b0010_1010);
+        assert_eq!(parse("0101").substituted_in(name)[0], 0b0101_1010);
+        assert_eq!(parse("01010101").substituted_in(name)[..2], [0x55, 0xAA]);
+
+        let mut rng = SmallRng::from_entropy();
+        for bit_count in 0..=8 * XOR_NAME_LEN {
+            let prefix = Prefix::new(bit_count, rng.gen());
+            let name: XorName = rng.gen();
+            let expected = (0..bit_count).fold(name, |acc, i| {
+                acc.with_bit(i as u8, prefix.name.bit(i as u8))
+            });
+            assert_eq!(prefix.substituted_in(name), expected);
+            assert!(prefix.matches(&prefix.substituted_in(name)));
+        }
+    }
+
+    #[test]
+    fn sample_names() {
+        assert_eq!(parse("").sample_names(0).count(), 0);
+        assert_eq!(
+            parse("").sample_names(1).collect::<Vec<_>>(),
+            [XorName::ZERO]
+        );
+        assert_eq!(
+            parse("").sample_names(4).collect::<Vec<_>>(),
+            [
+                xor_name!(0b0000_0000),
+                xor_name!(0b0100_0000),
+                xor_name!(0b1000_0000),
+                xor_name!(0b1100_0000),
+            ]
+        );
+        assert_eq!(
+            parse("1").sample_names(2).collect::<Vec<_>>(),
+            [xor_name!(0b1000_0000), xor_name!(0b1100_0000)]
+        );
+
+        // 2^256 / 3 = 0x5555...55 remainder 1
+        let names: Vec<_> = parse("").sample_names(3).collect();
+        assert_eq!(names[1], XorName([0x55; XOR_NAME_LEN]));
+        assert_eq!(names[2], XorName([0xAA; XOR_NAME_LEN]));
+
+        // More samples than names
+        let full = Prefix::new(255, XorName([0xAA; XOR_NAME_LEN]));
+        let names: Vec<_> = full.sample_names(5).collect();
+        assert_eq!(names.len(), 5);
+        assert!(names.iter().all(|name| full.matches(name)));
+        assert!(names.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(names[0], full.lower_bound());
+        assert_eq!(names[4], full.upper_bound());
+
+        // Evenly spaced and always inside the prefix
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..50 {
+            let prefix = Prefix::new(rng.gen_range(0..200), rng.gen());
+            let n = rng.gen_range(1..100);
+            let names: Vec<_> = prefix.sample_names(n).collect();
+            assert_eq!(names.len(), n);
+            assert_eq!(names[0], prefix.lower_bound());
+            assert!(names.iter().all(|name| prefix.matches(name)));
+            assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(prefix.sample_names(n).next_back(), names.last().copied());
+        }
+    }
+
+    #[test]
+    fn index() {
+        assert_eq!(parse("").to_index(), Some(0));
+        assert_eq!(parse("0").to_index(), Some(0));
+        assert_eq!(parse("1").to_index(), Some(1));
+        assert_eq!(parse("0110").to_index(), Some(6));
+        assert_eq!(Prefix::new(65, XorName::ZERO).to_index(), None);
+
+        assert_eq!(Prefix::from_index(0, 0), Some(parse("")));
+        assert_eq!(Prefix::from_index(0, 1), None);
+        assert_eq!(Prefix::from_index(3, 5), Some(parse("101")));
+        assert_eq!(Prefix::from_index(3, 8), None);
+        assert_eq!(Prefix::from_index(65, 0), None);
+        assert_eq!(
+            Prefix::from_index(64, u64::MAX),
+            Some(Prefix::new(64, XorName([0xff; XOR_NAME_LEN])))
+        );
+
+        // All prefixes of a given length, in order.
+        for bit_count in 0..=4 {
+            let prefixes: Vec<_> = parse("").subtree(bit_count).collect();
+            let level = prefixes.iter().filter(|p| p.bit_count() == bit_count);
+            for (i, prefix) in level.enumerate() {
+                assert_eq!(prefix.to_index(), Some(i as u64));
+                assert_eq!(Prefix::from_index(bit_count, i as u64), Some(*prefix));
+            }
+        }
+
+        let mut rng = SmallRng::from_entropy();
+        for bit_count in 0..=64 {
+            let prefix = Prefix::new(bit_count, rng.gen());
+            let index = prefix.to_index().unwrap();
+            assert_eq!(Prefix::from_index(bit_count, index), Some(prefix));
+        }
+    }
+
+    #[test]
+    fn min_distance_to() {
+        let mut rng = SmallRng::from_entropy();
+        for bit_count in 0..=8 * XOR_NAME_LEN {
+            let prefix = Prefix::new(bit_count, rng.gen());
+            let inside = prefix.substituted_in(rng.gen());
+            assert_eq!(prefix.min_distance_to(&inside), XorDistance::default());
+
+            let name: XorName = rng.gen();
+            let distance = prefix.min_distance_to(&name);
+            // The distance to the lower bound is an upper bound for the minimum distance.
+            assert!(distance <= name.distance(&prefix.lower_bound()));
+            assert!(distance <= name.distance(&prefix.upper_bound()));
+            if !prefix.matches(&name) {
+                assert_eq!(distance.leading_zeros(), prefix.common_prefix(&name));
+            }
+            // Only the significant bits can differ.
+            let distance = XorName::from(distance);
+            assert_eq!(Prefix::new(bit_count, distance).name(), distance);
+        }
+
+        // Brute force over all four names of a 254 bit prefix.
+        let prefix = Prefix::new(254, rng.gen());
+        let mut step = XorName::ZERO;
+        step[XOR_NAME_LEN - 1] = 1;
+        for _ in 0..100 {
+            let name: XorName = rng.gen();
+            let closest = prefix.names(step).map(|n| name.distance(&n)).min();
+            assert_eq!(Some(prefix.min_distance_to(&name)), closest);
+        }
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(parse("01").distance(&parse("0")), XorDistance::default());
+        assert_eq!(parse("01").distance(&parse("011")), XorDistance::default());
+        assert_eq!(parse("01").distance(&parse("1")).leading_zeros(), 0);
+        assert_eq!(parse("0110").distance(&parse("010")).leading_zeros(), 2);
+        assert_eq!(
+            XorName::from(parse("0110").distance(&parse("010"))),
+            parse("001").name()
+        );
+
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..100 {
+            let lhs = Prefix::new(rng.gen_range(0..=8 * XOR_NAME_LEN), rng.gen());
+            let rhs = Prefix::new(rng.gen_range(0..=8 * XOR_NAME_LEN), rng.gen());
+            let distance = lhs.distance(&rhs);
+            assert_eq!(distance, rhs.distance(&lhs));
+            assert_eq!(distance == XorDistance::default(), lhs.is_compatible(&rhs));
+            // Any name of the longer prefix is as close to the shorter one as can be.
+            let name = rng.gen();
+            assert_eq!(
+                distance,
+                if lhs.bit_count() <= rhs.bit_count() {
+                    lhs.min_distance_to(&rhs.substituted_in(name))
+                } else {
+                    rhs.min_distance_to(&lhs.substituted_in(name))
+                }
+            );
+        }
+
+        let target = parse("0110");
+        let mut prefixes: Vec<_> = ["1", "00", "0111", "", "01", "010", "01101"]
+            .iter()
+            .map(|bits| parse(bits))
+            .collect();
+        prefixes.sort_by(|lhs, rhs| lhs.cmp_distance_to_prefix(rhs, &target));
+        let sorted: Vec<_> = prefixes.iter().map(|prefix| prefix.to_string()).collect();
+        assert_eq!(sorted, ["", "01", "01101", "0111", "010", "00", "1"]);
+    }
+
+    #[test]
+    fn name_conversions() {
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let prefix = Prefix::from(name);
+        assert_eq!(prefix.bit_count(), 8 * XOR_NAME_LEN);
+        assert!(prefix.matches(&name));
+        assert_eq!(prefix.range_inclusive(), name..=name);
+        assert_eq!(XorName::try_from(prefix), Ok(name));
+
+        assert_eq!(
+            XorName::try_from(prefix.popped()),
+            Err(Error::IncompletePrefix(255))
+        );
+        assert_eq!(
+            XorName::try_from(parse("")),
+            Err(Error::IncompletePrefix(0))
+        );
+    }
+
+    #[test]
+    fn eq_and_ord() {
+        assert_eq!(parse("0101"), parse("0101"));
+        assert_ne!(parse("0101"), parse("010"));
+        assert_ne!(parse("0101"), parse("0100"));
+        assert_eq!(parse(""), parse(""));
+
+        // Insignificant bits are ignored, even if they weren't normalised.
+        let dirty = Prefix {
+            bit_count: 4,
+            name: xor_name!(0b0101_1111, 0xff),
+        };
+        assert_eq!(dirty, parse("0101"));
+        assert_eq!(dirty.cmp(&parse("0101")), Ordering::Equal);
+        assert_eq!(dirty.cmp(&parse("01011")), Ordering::Less);
+        assert_eq!(dirty.cmp(&parse("0110")), Ordering::Less);
+
+        let expected = [
+            parse(""),
+            parse("0"),
+            parse("00"),
+            parse("000"),
+            parse("001"),
+            parse("01"),
+            parse("010"),
+            parse("011"),
+            parse("1"),
+            parse("10"),
+            parse("100"),
+            parse("101"),
+            parse("11"),
+            parse("110"),
+            parse("111"),
+        ];
+        for (i, lhs) in expected.iter().enumerate() {
+            for (j, rhs) in expected.iter().enumerate() {
+                assert_eq!(lhs.cmp(rhs), i.cmp(&j), "{:?} vs {:?}", lhs, rhs);
+                assert_eq!(lhs == rhs, i == j);
+            }
+        }
     }
 
     #[test]
@@ -418,7 +1497,98 @@ mod tests {
             actual.sort_by(|lhs, rhs| lhs.cmp_breadth_first(rhs));
 
             assert_eq!(actual, expected);
+
+            actual.shuffle(&mut rng);
+            let set: BTreeSet<_> = actual.iter().copied().map(BreadthFirst).collect();
+            assert!(set.iter().map(|key| key.0).eq(expected.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn subtree() {
+        let collect = |prefix: &str, max_depth| {
+            parse(prefix)
+                .subtree(max_depth)
+                .map(|p| std::format!("{}", p))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(collect("", 0), [""]);
+        assert_eq!(collect("", 2), ["", "0", "1", "00", "01", "10", "11"]);
+        assert_eq!(collect("01", 2), ["01"]);
+        assert_eq!(collect("01", 1), Vec::<String>::new());
+        assert_eq!(
+            collect("01", 4),
+            ["01", "010", "011", "0100", "0101", "0110", "0111"]
+        );
+
+        // Each level is complete and in order.
+        let prefix = parse("101");
+        let subtree: Vec<_> = prefix.subtree(9).collect();
+        assert_eq!(subtree.len(), (1 << 7) - 1);
+        assert!(subtree
+            .iter()
+            .all(|p| p.is_extension_of(&prefix) || *p == prefix));
+        assert!(subtree
+            .windows(2)
+            .all(|pair| pair[0].cmp_breadth_first(&pair[1]) == Ordering::Less));
+
+        // The whole tree, and resuming it anywhere.
+        let all: Vec<_> = all_prefixes(6).collect();
+        assert_eq!(all, parse("").subtree(6).collect::<Vec<_>>());
+        for (i, prefix) in all.iter().enumerate() {
+            assert!(all_prefixes_from(prefix, 6).eq(all[i..].iter().copied()));
+            let len = all.len() - i;
+            assert_eq!(all_prefixes_from(prefix, 6).size_hint(), (len, Some(len)));
         }
+        assert_eq!(all_prefixes_from(&parse("0101"), 3).count(), 0);
+        assert_eq!(all_prefixes_from(&parse("11"), 2).count(), 1);
+        let full = Prefix::from(XorName([0xFF; XOR_NAME_LEN]));
+        assert!(all_prefixes_from(&full, usize::MAX).eq(Some(full)));
+
+        // Deep subtrees, up to and beyond the maximum depth.
+        let deep = Prefix::new(254, XorName([0xAA; XOR_NAME_LEN]));
+        assert_eq!(deep.subtree(256).count(), 7);
+        assert_eq!(deep.subtree(1000).count(), 7);
+        let full = Prefix::new(256, XorName([0xFF; XOR_NAME_LEN]));
+        assert_eq!(full.subtree(256).collect::<Vec<_>>(), [full]);
+    }
+
+    #[test]
+    fn subtree_size_hint() {
+        let mut subtree = parse("01").subtree(5);
+        for len in (0..=15).rev() {
+            assert_eq!(subtree.size_hint(), (len, Some(len)));
+            let _ = subtree.next();
+        }
+        assert_eq!(parse("01").subtree(1).size_hint(), (0, Some(0)));
+
+        let deep = Prefix::new(254, XorName([0xAA; XOR_NAME_LEN]));
+        assert_eq!(deep.subtree(1000).size_hint(), (7, Some(7)));
+
+        // Near the end of the deepest level, only the last few prefixes remain.
+        let mut name = XorName([0xFF; XOR_NAME_LEN]);
+        assert_eq!(
+            all_prefixes_from(&name.into(), 256).size_hint(),
+            (1, Some(1))
+        );
+        name[XOR_NAME_LEN - 1] = 0;
+        assert_eq!(
+            all_prefixes_from(&name.into(), 256).size_hint(),
+            (256, Some(256))
+        );
+
+        // Counts that don't fit in a `usize` saturate.
+        assert_eq!(all_prefixes(256).size_hint(), (usize::MAX, None));
+        assert_eq!(
+            all_prefixes(usize::BITS as usize).size_hint(),
+            (usize::MAX, None)
+        );
+        let start = Prefix::new(200, XorName::ZERO);
+        assert_eq!(
+            all_prefixes_from(&start, 200).size_hint(),
+            (usize::MAX, None)
+        );
     }
 
     #[test]
@@ -440,6 +1610,174 @@ mod tests {
         assert_eq!(ancestors.next(), Some(parse("0")));
         assert_eq!(ancestors.next(), Some(parse("01")));
         assert_eq!(ancestors.next(), None);
+        assert_eq!(ancestors.next(), None);
+
+        let mut ancestors = parse("011").ancestors();
+        assert_eq!(ancestors.len(), 3);
+        assert_eq!(ancestors.next_back(), Some(parse("01")));
+        assert_eq!(ancestors.next(), Some(parse("")));
+        assert_eq!(ancestors.len(), 1);
+        assert_eq!(ancestors.next_back(), Some(parse("0")));
+        assert_eq!(ancestors.len(), 0);
+        assert_eq!(ancestors.next(), None);
+        assert_eq!(ancestors.next_back(), None);
+
+        let full = Prefix::from(XorName([0xAA; XOR_NAME_LEN]));
+        let deepest_first: Vec<_> = full.ancestors().rev().collect();
+        assert_eq!(deepest_first.len(), 8 * XOR_NAME_LEN);
+        assert_eq!(deepest_first[0], full.popped());
+        assert_eq!(deepest_first[8 * XOR_NAME_LEN - 1], parse(""));
+    }
+
+    #[test]
+    fn try_ancestor() {
+        assert_eq!(parse("011").try_ancestor(0), Ok(parse("")));
+        assert_eq!(parse("011").try_ancestor(2), Ok(parse("01")));
+        assert_eq!(parse("011").ancestor(2), parse("01"));
+        assert_eq!(parse("011").try_ancestor(3), Err(Error::NotAnAncestor(3)));
+        assert_eq!(parse("").try_ancestor(0), Err(Error::NotAnAncestor(0)));
+        assert_eq!(
+            parse("0").try_ancestor(usize::MAX),
+            Err(Error::NotAnAncestor(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn names() {
+        let (mut one, mut two) = (XorName::ZERO, XorName::ZERO);
+        one[XOR_NAME_LEN - 1] = 1;
+        two[XOR_NAME_LEN - 1] = 2;
+
+        // The four names of a 254 bit prefix.
+        let prefix = Prefix::new(254, XorName([0x5a; XOR_NAME_LEN]));
+        let names: Vec<_> = prefix.names(one).collect();
+        assert_eq!(names.len(), 4);
+        assert_eq!(names[0], prefix.lower_bound());
+        assert_eq!(names[3], prefix.upper_bound());
+        assert!(names.iter().all(|name| prefix.matches(name)));
+        assert!(names
+            .windows(2)
+            .all(|pair| pair[0].successor() == Some(pair[1])));
+        assert_eq!(prefix.names(two).count(), 2);
+        assert_eq!(prefix.names(xor_name!(1)).count(), 1);
+        assert_eq!(prefix.names(XorName::ZERO).count(), 1);
+
+        // Stepping past the very last name doesn't wrap around.
+        let names: Vec<_> = parse("").names(xor_name!(0x80)).collect();
+        assert_eq!(names, [xor_name!(), xor_name!(0x80)]);
+        let full = Prefix::new(256, XorName([0xff; XOR_NAME_LEN]));
+        assert_eq!(full.names(one).collect::<Vec<_>>(), [full.name()]);
+
+        // A step that doesn't divide the range: 0110_0000.., 0111_1000..
+        let names: Vec<_> = parse("011").names(xor_name!(0b0001_1000)).collect();
+        assert_eq!(names, [xor_name!(0b0110_0000), xor_name!(0b0111_1000)]);
+    }
+
+    #[test]
+    fn names_double_ended() {
+        let (mut one, mut five, mut sixty) = (XorName::ZERO, XorName::ZERO, XorName::ZERO);
+        one[XOR_NAME_LEN - 1] = 1;
+        five[XOR_NAME_LEN - 1] = 5;
+        sixty[XOR_NAME_LEN - 1] = 60;
+
+        let prefix = Prefix::new(250, XorName([0x5a; XOR_NAME_LEN]));
+        for step in [one, five, xor_name!(1), XorName::ZERO] {
+            let names: Vec<_> = prefix.names(step).collect();
+            let len = names.len();
+            assert_eq!(prefix.names(step).size_hint(), (len, Some(len)));
+            assert!(prefix.names(step).rev().eq(names.iter().rev().copied()));
+        }
+
+        // 64 names in steps of 5: the last one is 60 from the lower bound, not the upper bound.
+        let mut names = prefix.names(five);
+        assert_eq!(names.size_hint(), (13, Some(13)));
+        assert_eq!(names.next_back(), prefix.lower_bound().checked_add(&sixty));
+        assert_eq!(names.next(), Some(prefix.lower_bound()));
+        assert_eq!(names.size_hint(), (11, Some(11)));
+        assert_eq!(names.by_ref().rev().count(), 11);
+        assert_eq!(names.size_hint(), (0, Some(0)));
+        assert_eq!(names.next(), None);
+        assert_eq!(names.next_back(), None);
+
+        // Stepping back from the last aligned name: 0110_0000.., 0111_1000..
+        let mut names = parse("011").names(xor_name!(0b0001_1000));
+        assert_eq!(names.next_back(), Some(xor_name!(0b0111_1000)));
+        assert_eq!(names.size_hint(), (1, Some(1)));
+        assert_eq!(names.next_back(), Some(xor_name!(0b0110_0000)));
+        assert!(names.remaining().is_none());
+
+        // The whole name space, one name at a time, is too many to count.
+        assert_eq!(parse("").names(one).size_hint(), (usize::MAX, None));
+        assert_eq!(
+            parse("").names(one).next_back(),
+            Some(XorName([0xFF; XOR_NAME_LEN]))
+        );
+    }
+
+    #[test]
+    fn sibling_chain() {
+        assert_eq!(parse("").sibling_chain().count(), 0);
+        assert!(parse("0").sibling_chain().eq([parse("1")]));
+
+        let mut rng = SmallRng::from_entropy();
+        for &len in &[1, 7, 64, 256] {
+            let prefix = Prefix::new(len, rng.gen());
+            let chain: Vec<_> = prefix.sibling_chain().collect();
+            assert_eq!(chain.len(), len);
+            assert!(prefix.sibling_chain().rev().eq(chain.iter().rev().copied()));
+            for (k, sibling) in chain.iter().enumerate() {
+                assert_eq!(sibling.bit_count(), k + 1);
+                assert_eq!(sibling.common_prefix(&prefix.name()), k);
+                assert!(sibling.with_flipped_bit(k as u8).is_compatible(&prefix));
+            }
+            let mut all = chain.clone();
+            all.push(prefix);
+            assert!(parse("").is_covered_by(&all));
+            all.sort();
+            assert!(all.windows(2).all(|pair| !pair[0].is_compatible(&pair[1])));
+        }
+    }
+
+    #[test]
+    fn names_between() {
+        let mut rng = SmallRng::from_entropy();
+        let (a, b): (XorName, XorName) = (rng.gen(), rng.gen());
+        let (start, end) = (a.min(b), a.max(b));
+        let step = xor_name!(0, rng.gen_range(1..=u8::MAX));
+
+        // Stopping at any point and resuming gives the same names as a single sweep.
+        let all: Vec<_> = XorName::names_between(start..=end, step).collect();
+        assert_eq!(all[0], start);
+        assert!(all.iter().all(|name| *name <= end));
+        assert!(all
+            .windows(2)
+            .all(|pair| pair[0].checked_add(&step) == Some(pair[1])));
+        let stop = rng.gen_range(0..=all.len());
+        let mut names = XorName::names_between(start..=end, step);
+        let mut resumed: Vec<_> = names.by_ref().take(stop).collect();
+        if let Some(remaining) = names.remaining() {
+            resumed.extend(XorName::names_between(remaining, step));
+        }
+        assert_eq!(resumed, all);
+        assert!(XorName::names_between(start..=end, step)
+            .rev()
+            .eq(all.iter().rev().copied()));
+        assert_eq!(
+            XorName::names_between(start..=end, step).size_hint(),
+            (all.len(), Some(all.len()))
+        );
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = XorName::names_between(xor_name!(1)..=XorName::ZERO, step);
+        assert!(empty.remaining().is_none());
+        assert_eq!(empty.count(), 0);
+        assert_eq!(
+            XorName::names_between(start..=end, XorName::ZERO).count(),
+            1
+        );
+        let mut single = XorName::names_between(end..=end, step);
+        assert_eq!(single.next(), Some(end));
+        assert!(single.remaining().is_none());
     }
 
     #[test]
@@ -453,7 +1791,180 @@ mod tests {
         assert_eq!(&format!(7, "{:b}", parse("1100101")), "1100101");
 
         // Bit string with 257 width
-        assert!(Prefix::from_str(&"1".repeat(XOR_NAME_LEN * 8 + 1)).is_err());
+        assert_eq!(
+            Prefix::from_str(&"1".repeat(XOR_NAME_LEN * 8 + 1)),
+            Err(Error::TooLong(XOR_NAME_LEN * 8 + 1))
+        );
+        assert_eq!(Prefix::from_str("0120"), Err(Error::InvalidChar('2')));
+        assert_eq!(Prefix::try_from("0120"), Err(Error::InvalidChar('2')));
+        assert_eq!(Prefix::try_from("1100101"), Ok(parse("1100101")));
+        assert_eq!(
+            Prefix::try_from("1100_101".to_owned()),
+            Ok(parse("1100101"))
+        );
+    }
+
+    #[test]
+    fn format_debug() {
+        assert_eq!(&format!(16, "{:?}", parse("")), "Prefix()");
+        assert_eq!(&format!(16, "{:?}", parse("0101")), "Prefix(0101)");
+        assert_eq!(
+            &format!(256, "{:#?}", parse("0101")),
+            "Prefix {\n    \
+             bit_count: 4,\n    \
+             bits: \"0101\",\n    \
+             lower_bound: 5000000000000000000000000000000000000000000000000000000000000000,\n    \
+             upper_bound: 5fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff,\n\
+             }"
+        );
+    }
+
+    #[test]
+    fn deserialize_rejects_malformed() {
+        // The compact (bincode) encoding is the bit count as little endian `u16`, then the name.
+        let encode = |bit_count: u16, name: XorName| {
+            let mut bytes = bit_count.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&name.0);
+            bytes
+        };
+        let decode = |bytes: &[u8]| bincode::deserialize::<Prefix>(bytes);
+
+        let name = XorName([0xAA; XOR_NAME_LEN]);
+        for bit_count in [0, 1, 7, 100, 255, 256] {
+            let prefix = decode(&encode(bit_count, name)).unwrap();
+            assert_eq!(prefix, Prefix::new(bit_count as usize, name));
+            // Insignificant bits are cleared, so the name round-trips through `Prefix::new`.
+            assert_eq!(prefix.name(), Prefix::new(bit_count as usize, name).name());
+            assert_eq!(
+                decode(&bincode::serialize(&prefix).unwrap())
+                    .unwrap()
+                    .name(),
+                prefix.name()
+            );
+        }
+
+        assert!(decode(&encode(257, name)).is_err());
+        assert!(decode(&encode(u16::MAX, name)).is_err());
+    }
+
+    #[test]
+    fn format_separated() {
+        assert_eq!(&format!(0, "{:#}", parse("")), "");
+        assert_eq!(&format!(4, "{:#}", parse("0110")), "0110");
+        assert_eq!(&format!(8, "{:#}", parse("10110010")), "10110010");
+        assert_eq!(&format!(16, "{:#}", parse("101100100111")), "10110010_0111");
+        assert_eq!(
+            &format!(32, "{:#}", parse("000000001111111100000000")),
+            "00000000_11111111_00000000"
+        );
+
+        assert_eq!(parse("10110010_0111"), parse("101100100111"));
+        assert_eq!(parse("_1_0_"), parse("10"));
+        assert_eq!(parse("___"), parse(""));
+
+        let mut rng = SmallRng::from_entropy();
+        for bit_count in 0..=8 * XOR_NAME_LEN {
+            let prefix = Prefix::new(bit_count, rng.gen());
+            let separated = std::format!("{:#}", prefix);
+            assert_eq!(separated.replace('_', ""), std::format!("{}", prefix));
+            assert_eq!(parse(&separated), prefix);
+        }
+
+        // Separators don't count towards the length limit.
+        let separated = std::format!("{:#}", Prefix::new(256, XorName([0xff; XOR_NAME_LEN])));
+        assert_eq!(separated.len(), 256 + 31);
+        assert!(Prefix::from_str(&separated).is_ok());
+        assert_eq!(
+            Prefix::from_str(&std::format!("{}_1", separated)),
+            Err(Error::TooLong(257))
+        );
+    }
+
+    #[test]
+    fn pushed_bits() {
+        assert_eq!(parse("").pushed_bits(vec![]), parse(""));
+        assert_eq!(
+            parse("1").pushed_bits([false, true, true].iter().copied()),
+            parse("1011")
+        );
+
+        let mut rng = SmallRng::from_entropy();
+        let name: XorName = rng.gen();
+        let bits = (0..=255).map(|i| name.bit(i));
+        let prefix = parse("").pushed_bits(bits.clone());
+        assert_eq!(prefix, Prefix::from(name));
+        // Saturates at the maximum length.
+        assert_eq!(parse("").pushed_bits(bits.chain(Some(true))), prefix);
+        assert_eq!(
+            Prefix::new(100, name).pushed_bits((100..200).map(|i| name.bit(i))),
+            Prefix::new(200, name)
+        );
+    }
+
+    #[test]
+    fn append() {
+        assert_eq!(parse("").append(&parse("")), parse(""));
+        assert_eq!(parse("01").append(&parse("")), parse("01"));
+        assert_eq!(parse("").append(&parse("01")), parse("01"));
+        assert_eq!(parse("1").append(&parse("0110")), parse("10110"));
+
+        let mut rng = SmallRng::from_entropy();
+        let (lhs, rhs) = (Prefix::new(200, rng.gen()), Prefix::new(100, rng.gen()));
+        let appended = lhs.append(&rhs);
+        // Saturates at the maximum length.
+        assert_eq!(appended.bit_count(), 256);
+        assert!(appended.is_extension_of(&lhs));
+        assert!((0..56).all(|i| appended.name().bit(200 + i) == rhs.name().bit(i)));
+        assert_eq!(
+            Prefix::from(lhs.name()).append(&rhs),
+            Prefix::from(lhs.name())
+        );
+    }
+
+    #[test]
+    fn bulk_matching() {
+        let mut rng = SmallRng::from_entropy();
+        for &len in &[0, 1, 7, 8, 9, 63, 64, 200, 255, 256] {
+            let prefix = Prefix::new(len, rng.gen());
+            // Names close to the prefix, so that all bits are tested.
+            let names: Vec<XorName> = (0..150)
+                .map(|_| {
+                    let name = prefix.substituted_in(rng.gen());
+                    match rng.gen_range(0..=len) {
+                        0 => name,
+                        bit => name.with_flipped_bit((bit - 1) as u8),
+                    }
+                })
+                .collect();
+
+            let (matching, other) = prefix.partition(&names);
+            assert!(matching.iter().all(|name| prefix.matches(name)));
+            assert!(other.iter().all(|name| !prefix.matches(name)));
+            assert_eq!(matching.len() + other.len(), names.len());
+
+            let mask = prefix.matches_many(&names);
+            assert_eq!(mask.len(), 3);
+            for (i, name) in names.iter().enumerate() {
+                assert_eq!(mask[i / 64] & 1 << (i % 64) != 0, prefix.matches(name));
+            }
+        }
+        assert!(parse("1").matches_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn prefix_macro() {
+        const PREFIX: Prefix = prefix!("1011_0010_01");
+        assert_eq!(PREFIX, parse("1011001001"));
+        assert_eq!(prefix!(""), parse(""));
+        assert_eq!(prefix!("0"), parse("0"));
+
+        let mut rng = SmallRng::from_entropy();
+        for bit_count in 0..=8 * XOR_NAME_LEN {
+            let prefix = Prefix::new(bit_count, rng.gen());
+            let parsed = Prefix::__parse_const(&std::format!("{:#}", prefix));
+            assert_eq!(parsed, prefix);
+            assert_eq!(parsed.name(), prefix.name());
+        }
     }
 
     #[test]
@@ -0,0 +1,302 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Python bindings for `XorName` and `Prefix`, enabled with the `python` feature.
+//!
+//! Build the extension module with `maturin build` (see `pyproject.toml`); it is importable as
+//! `xor_name`.
+
+use crate::{closest_first_bounded, Prefix, XorName, XOR_NAME_LEN};
+use core::{cmp::Ordering, convert::TryFrom, str::FromStr};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+use rand_core::OsRng;
+
+/// `XorName` as exposed to Python.
+#[pyclass(name = "XorName", module = "xor_name", frozen, eq, ord, hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyXorName(XorName);
+
+#[pymethods]
+impl PyXorName {
+    /// Creates a name from exactly 32 bytes.
+    #[new]
+    fn new(bytes: &[u8]) -> PyResult<Self> {
+        XorName::try_from(bytes)
+            .map(Self)
+            .map_err(|e| PyValueError::new_err(std::format!("{}", e)))
+    }
+
+    /// Parses a name from a 64 character hex string.
+    #[staticmethod]
+    fn from_hex(hex: &str) -> PyResult<Self> {
+        XorName::from_str(hex)
+            .map(Self)
+            .map_err(|e| PyValueError::new_err(std::format!("{}", e)))
+    }
+
+    /// Generates the name for the given content.
+    #[staticmethod]
+    fn from_content(content: &[u8]) -> Self {
+        Self(XorName::from_content(content))
+    }
+
+    /// Generates a random name using the operating system's secure random source.
+    #[staticmethod]
+    fn random() -> Self {
+        Self(XorName::random(&mut OsRng))
+    }
+
+    /// Returns `True` if the `i`-th bit is `1`.
+    fn bit(&self, i: u8) -> bool {
+        self.0.bit(i)
+    }
+
+    /// Compares the distance of the arguments to `self`. Returns `-1` if `lhs` is closer, `1` if
+    /// `rhs` is closer and `0` if `lhs == rhs`.
+    fn cmp_distance(&self, lhs: &Self, rhs: &Self) -> i8 {
+        ordering_to_i8(self.0.cmp_distance(&lhs.0, &rhs.0))
+    }
+
+    /// Returns the full lowercase hex representation of the name.
+    fn hex(&self) -> String {
+        std::format!("{:x}", self.0)
+    }
+
+    fn __bytes__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0 .0)
+    }
+
+    fn __str__(&self) -> String {
+        std::format!("{}", self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        std::format!("XorName.from_hex('{:x}')", self.0)
+    }
+}
+
+/// `Prefix` as exposed to Python.
+#[pyclass(name = "Prefix", module = "xor_name", frozen, eq, ord, hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyPrefix(Prefix);
+
+#[pymethods]
+impl PyPrefix {
+    /// Parses a prefix from a string of `0`s and `1`s.
+    #[new]
+    fn new(bits: &str) -> PyResult<Self> {
+        Prefix::from_str(bits)
+            .map(Self)
+            .map_err(|e| PyValueError::new_err(std::format!("{}", e)))
+    }
+
+    /// Creates a prefix with the first `bit_count` bits of `name`.
+    #[staticmethod]
+    fn from_name(bit_count: usize, name: &PyXorName) -> Self {
+        Self(Prefix::new(bit_count, name.0))
+    }
+
+    /// The number of bits in the prefix.
+    #[getter]
+    fn bit_count(&self) -> usize {
+        self.0.bit_count()
+    }
+
+    /// The name of this prefix.
+    #[getter]
+    fn name(&self) -> PyXorName {
+        PyXorName(self.0.name())
+    }
+
+    /// Returns `True` if this is a prefix of the given `name`.
+    fn matches(&self, name: &PyXorName) -> bool {
+        self.0.matches(&name.0)
+    }
+
+    /// Returns `True` if `self` is a prefix of `other` or vice versa.
+    fn is_compatible(&self, other: &Self) -> bool {
+        self.0.is_compatible(&other.0)
+    }
+
+    /// Returns `True` if `other` is compatible but strictly shorter than `self`.
+    fn is_extension_of(&self, other: &Self) -> bool {
+        self.0.is_extension_of(&other.0)
+    }
+
+    /// Returns `True` if the `other` prefix differs in exactly one bit from this one.
+    fn is_neighbour(&self, other: &Self) -> bool {
+        self.0.is_neighbour(&other.0)
+    }
+
+    /// Compares the distance of `self` and `other` to `target`. Returns `-1` if `self` is closer,
+    /// `1` if `other` is closer, with ties broken deterministically.
+    fn cmp_distance(&self, other: &Self, target: &PyXorName) -> i8 {
+        ordering_to_i8(self.0.cmp_distance(&other.0, &target.0))
+    }
+
+    /// Returns the prefix extended by one bit.
+    fn pushed(&self, bit: bool) -> Self {
+        Self(self.0.pushed(bit))
+    }
+
+    /// Returns the prefix with its last bit removed.
+    fn popped(&self) -> Self {
+        Self(self.0.popped())
+    }
+
+    /// Returns the same prefix with the last bit flipped.
+    fn sibling(&self) -> Self {
+        Self(self.0.sibling())
+    }
+
+    /// Returns all ancestors of this prefix, starting at the empty prefix.
+    fn ancestors(&self) -> Vec<Self> {
+        self.0.ancestors().map(Self).collect()
+    }
+
+    /// Returns the smallest name matching the prefix.
+    fn lower_bound(&self) -> PyXorName {
+        PyXorName(self.0.lower_bound())
+    }
+
+    /// Returns the largest name matching the prefix.
+    fn upper_bound(&self) -> PyXorName {
+        PyXorName(self.0.upper_bound())
+    }
+
+    fn __str__(&self) -> String {
+        std::format!("{}", self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        std::format!("Prefix('{}')", self.0)
+    }
+}
+
+/// Returns `names` sorted by their XOR distance to `target`, closest first.
+#[pyfunction]
+fn sort_by_distance(target: &PyXorName, mut names: Vec<PyXorName>) -> Vec<PyXorName> {
+    names.sort_by(|lhs, rhs| target.0.cmp_distance(&lhs.0, &rhs.0));
+    names
+}
+
+/// Returns the `count` names closest to `target`, closest first.
+#[pyfunction]
+fn closest(target: &PyXorName, names: Vec<PyXorName>, count: usize) -> Vec<PyXorName> {
+    closest_first_bounded(names.iter().map(|name| name.0), &target.0, count)
+        .map(PyXorName)
+        .collect()
+}
+
+#[pymodule]
+#[pyo3(name = "xor_name")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyXorName>()?;
+    m.add_class::<PyPrefix>()?;
+    m.add_function(wrap_pyfunction!(sort_by_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(closest, m)?)?;
+    m.add("XOR_NAME_LEN", XOR_NAME_LEN)?;
+    Ok(())
+}
+
+fn ordering_to_i8(ordering: Ordering) -> i8 {
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn from_hex() {
+        let name = PyXorName::from_hex(HEX).unwrap();
+        assert_eq!(name.hex(), HEX);
+        assert!(PyXorName::from_hex(&HEX.to_uppercase()).unwrap() == name);
+    }
+
+    #[test]
+    fn from_invalid_hex() {
+        // Odd lengths, including one digit short of a full name.
+        assert!(PyXorName::from_hex(&HEX[1..]).is_err());
+        assert!(PyXorName::from_hex(&[HEX, "0"].concat()).is_err());
+        // Even lengths other than 64.
+        assert!(PyXorName::from_hex(&HEX[2..]).is_err());
+        assert!(PyXorName::from_hex("").is_err());
+        // Signs and other non-digits.
+        assert!(PyXorName::from_hex(&["+f", &HEX[2..]].concat()).is_err());
+        assert!(PyXorName::from_hex(&["-1", &HEX[2..]].concat()).is_err());
+        assert!(PyXorName::from_hex(&["0x", &HEX[2..]].concat()).is_err());
+    }
+
+    fn name(first_byte: u8) -> PyXorName {
+        PyXorName(xor_name!(first_byte))
+    }
+
+    #[test]
+    fn closest_names() {
+        let target = name(0b0100_0000);
+        let names = vec![
+            name(0b1000_0000),
+            name(0b0110_0000),
+            name(0b0100_0001),
+            name(0b0000_0000),
+            name(0b0110_0000),
+        ];
+        let expected = [
+            name(0b0100_0001),
+            name(0b0110_0000),
+            name(0b0110_0000),
+            name(0b0000_0000),
+            name(0b1000_0000),
+        ];
+
+        assert!(sort_by_distance(&target, names.clone()) == expected);
+        assert!(closest(&target, names.clone(), 3) == expected[..3]);
+        assert!(closest(&target, names.clone(), 10) == expected);
+        assert!(closest(&target, names, 0).is_empty());
+        assert!(closest(&target, vec![], 3).is_empty());
+    }
+
+    #[test]
+    fn prefix_matching_and_ordering() {
+        let prefix = PyPrefix::new("01").unwrap();
+        assert!(PyPrefix::new("012").is_err());
+        assert!(prefix.matches(&name(0b0110_0000)));
+        assert!(!prefix.matches(&name(0b1110_0000)));
+        assert!(prefix.matches(&prefix.lower_bound()));
+        assert!(prefix.matches(&prefix.upper_bound()));
+        assert!(PyPrefix::from_name(2, &name(0b0111_1111)) == prefix);
+
+        let child = prefix.pushed(true);
+        assert!(child.is_extension_of(&prefix));
+        assert!(child.is_compatible(&prefix));
+        assert!(child.popped() == prefix);
+        assert!(prefix.sibling().is_neighbour(&prefix));
+        assert_eq!(prefix.ancestors().len(), 2);
+
+        // Ordered like `Prefix`: by name, then shorter first.
+        let mut prefixes: Vec<_> = ["1", "", "011", "01", "0"]
+            .iter()
+            .map(|bits| PyPrefix::new(bits).unwrap())
+            .collect();
+        prefixes.sort();
+        let sorted: Vec<_> = prefixes.iter().map(PyPrefix::__str__).collect();
+        assert_eq!(sorted, ["", "0", "01", "011", "1"]);
+
+        let target = name(0b0100_0000);
+        assert_eq!(prefix.cmp_distance(&prefix.sibling(), &target), -1);
+        assert_eq!(prefix.sibling().cmp_distance(&prefix, &target), 1);
+    }
+}
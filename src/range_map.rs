@@ -0,0 +1,251 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::XorName;
+use core::{iter::FromIterator, ops::RangeInclusive};
+
+/// A collection of values, each attached to an arbitrary range of names.
+///
+/// Unlike prefixes, the ranges don't need to be aligned to powers of two, and they may overlap.
+/// The map answers which ranges contain a name ([`XorRangeMap::values_covering`]) and which
+/// ones overlap a given range ([`XorRangeMap::overlapping`]), in `O(log n + k)` time for `k`
+/// results when the ranges are mostly disjoint.
+///
+/// ```
+/// use xor_name::{xor_name, XorRangeMap};
+///
+/// let mut leases = XorRangeMap::new();
+/// leases.insert(xor_name!(0x10)..=xor_name!(0x30), "repair");
+/// leases.insert(xor_name!(0x20)..=xor_name!(0x60), "replicate");
+///
+/// let covering: Vec<_> = leases.values_covering(&xor_name!(0x25)).map(|(_, v)| *v).collect();
+/// assert_eq!(covering, ["repair", "replicate"]);
+/// let overlapping: Vec<_> = leases
+///     .overlapping(&(xor_name!(0x40)..=xor_name!(0x50)))
+///     .map(|(_, v)| *v)
+///     .collect();
+/// assert_eq!(overlapping, ["replicate"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct XorRangeMap<T> {
+    // Sorted by the start of the range.
+    entries: Vec<Entry<T>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    range: RangeInclusive<XorName>,
+    value: T,
+    // The largest end of this and all preceding ranges, which is non-decreasing along `entries`.
+    max_end: XorName,
+}
+
+impl<T> XorRangeMap<T> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the number of ranges in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map holds no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Attaches `value` to `range`. Returns `false`, leaving the map unchanged, if the range is
+    /// empty.
+    pub fn insert(&mut self, range: RangeInclusive<XorName>, value: T) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+        let index = self
+            .entries
+            .partition_point(|entry| entry.range.start() <= range.start());
+        self.entries.insert(
+            index,
+            Entry {
+                max_end: *range.end(),
+                range,
+                value,
+            },
+        );
+        self.update_max_ends(index);
+        true
+    }
+
+    /// Removes all ranges and values for which `keep` returns `false`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&RangeInclusive<XorName>, &T) -> bool,
+    {
+        self.entries
+            .retain(|entry| keep(&entry.range, &entry.value));
+        self.update_max_ends(0);
+    }
+
+    /// Returns all ranges with their values, ordered by the start of the range.
+    pub fn iter(&self) -> impl Iterator<Item = (&RangeInclusive<XorName>, &T)> {
+        self.entries
+            .iter()
+            .map(|entry| (&entry.range, &entry.value))
+    }
+
+    /// Returns the ranges containing `name` with their values, ordered by the start of the
+    /// range.
+    pub fn values_covering<'a>(
+        &'a self,
+        name: &XorName,
+    ) -> impl Iterator<Item = (&'a RangeInclusive<XorName>, &'a T)> {
+        self.overlapping(&(*name..=*name))
+    }
+
+    /// Returns the ranges sharing at least one name with `range`, with their values, ordered by
+    /// the start of the range.
+    pub fn overlapping<'a>(
+        &'a self,
+        range: &RangeInclusive<XorName>,
+    ) -> impl Iterator<Item = (&'a RangeInclusive<XorName>, &'a T)> {
+        let (start, end) = (*range.start(), *range.end());
+        // Only entries starting at or before `end`, and having an end at or after `start` among
+        // them or their predecessors, can overlap.
+        let candidates = if start <= end {
+            let last = self
+                .entries
+                .partition_point(|entry| *entry.range.start() <= end);
+            let first = self.entries[..last].partition_point(|entry| entry.max_end < start);
+            &self.entries[first..last]
+        } else {
+            &[]
+        };
+        candidates
+            .iter()
+            .filter(move |entry| *entry.range.end() >= start)
+            .map(|entry| (&entry.range, &entry.value))
+    }
+
+    fn update_max_ends(&mut self, from: usize) {
+        let mut max_end = from
+            .checked_sub(1)
+            .map_or(XorName::ZERO, |i| self.entries[i].max_end);
+        for entry in &mut self.entries[from..] {
+            max_end = max_end.max(*entry.range.end());
+            entry.max_end = max_end;
+        }
+    }
+}
+
+impl<T> Default for XorRangeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(RangeInclusive<XorName>, T)> for XorRangeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (RangeInclusive<XorName>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T> Extend<(RangeInclusive<XorName>, T)> for XorRangeMap<T> {
+    fn extend<I: IntoIterator<Item = (RangeInclusive<XorName>, T)>>(&mut self, iter: I) {
+        self.entries
+            .extend(iter.into_iter().filter(|(range, _)| !range.is_empty()).map(
+                |(range, value)| Entry {
+                    max_end: *range.end(),
+                    range,
+                    value,
+                },
+            ));
+        self.entries
+            .sort_by(|lhs, rhs| lhs.range.start().cmp(rhs.range.start()));
+        self.update_max_ends(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Prefix;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn matches_linear_scan() {
+        let mut rng = SmallRng::from_entropy();
+        let random_range = |rng: &mut SmallRng| {
+            if rng.gen() {
+                Prefix::new(rng.gen_range(0..6), rng.gen()).range_inclusive()
+            } else {
+                let (a, b): (XorName, XorName) = (rng.gen(), rng.gen());
+                a.min(b)..=a.max(b)
+            }
+        };
+
+        let ranges: Vec<_> = (0..200).map(|_| random_range(&mut rng)).collect();
+        let mut map = XorRangeMap::new();
+        for (i, range) in ranges.iter().enumerate() {
+            assert!(map.insert(range.clone(), i));
+        }
+        assert_eq!(map.len(), ranges.len());
+        let collected: XorRangeMap<usize> = ranges.iter().cloned().zip(0..).collect();
+
+        for _ in 0..200 {
+            let query = random_range(&mut rng);
+            let mut expected: Vec<usize> = (0..ranges.len())
+                .filter(|i| ranges[*i].start() <= query.end() && ranges[*i].end() >= query.start())
+                .collect();
+            expected.sort_by_key(|i| *ranges[*i].start());
+            let actual: Vec<usize> = map.overlapping(&query).map(|(_, i)| *i).collect();
+            assert_eq!(actual.len(), expected.len());
+            assert!(actual
+                .iter()
+                .zip(&expected)
+                .all(|(lhs, rhs)| ranges[*lhs].start() == ranges[*rhs].start()));
+            assert_eq!(collected.overlapping(&query).count(), expected.len());
+
+            let name = *query.start();
+            let covering: Vec<usize> = map.values_covering(&name).map(|(_, i)| *i).collect();
+            assert!(covering.iter().all(|i| ranges[*i].contains(&name)));
+            assert_eq!(
+                covering.len(),
+                ranges.iter().filter(|range| range.contains(&name)).count()
+            );
+        }
+
+        map.retain(|_, i| i % 2 == 0);
+        assert_eq!(map.len(), 100);
+        for range in &ranges {
+            assert!(map
+                .values_covering(range.end())
+                .all(|(range, i)| i % 2 == 0 && range.contains(range.end())));
+        }
+    }
+
+    #[test]
+    fn empty_ranges() {
+        let (low, high) = (XorName::ZERO, XorName([0xFF; 32]));
+        let mut map = XorRangeMap::new();
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = high..=low;
+        assert!(!map.insert(empty.clone(), 0));
+        assert!(map.is_empty());
+
+        assert!(map.insert(low..=high, 1));
+        assert_eq!(map.overlapping(&empty).count(), 0);
+        assert_eq!(map.values_covering(&high).count(), 1);
+        assert_eq!(map.iter().count(), 1);
+    }
+}
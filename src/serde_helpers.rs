@@ -0,0 +1,493 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Modules for use with `#[serde(with = "...")]`, to choose the representation of a `XorName`
+//! field independently of the serialization format.
+//!
+//! The [`option`], [`vec`](mod@vec) and [`btree_set`] modules handle collections of names. In human
+//! readable formats they use hex strings, and otherwise a single blob of packed bytes, which
+//! is much smaller and faster than the default representation of one tuple per name.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use xor_name::XorName;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Record {
+//!     // Always a 64 character hex string.
+//!     #[serde(with = "xor_name::serde_helpers::hex")]
+//!     owner: XorName,
+//!     // Always a single 32 byte blob.
+//!     #[serde(with = "xor_name::serde_helpers::bytes")]
+//!     content: XorName,
+//! }
+//! ```
+
+use crate::{XorName, XOR_NAME_LEN};
+use core::{convert::TryFrom, fmt, iter::FromIterator};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Serializes a `XorName` as a lowercase hex string, in every format.
+pub mod hex {
+    use super::*;
+
+    /// Serializes `name` as a 64 character hex string.
+    pub fn serialize<S: Serializer>(name: &XorName, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:x}", name))
+    }
+
+    /// Deserializes a name from a 64 character hex string, in either case.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<XorName, D::Error> {
+        deserializer.deserialize_str(HexVisitor)
+    }
+}
+
+/// Serializes a `XorName` as a byte string, in every format.
+///
+/// Formats without native byte strings (such as JSON) represent it as a sequence of 32 numbers.
+pub mod bytes {
+    use super::*;
+
+    /// Serializes `name` as its 32 bytes.
+    pub fn serialize<S: Serializer>(name: &XorName, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&name.0)
+    }
+
+    /// Deserializes a name from exactly 32 bytes.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<XorName, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+/// Serializes an `Option<XorName>` as an optional hex string in human readable formats, and as
+/// optional bytes otherwise.
+pub mod option {
+    use super::*;
+
+    /// Serializes `name` as an optional hex string or byte string.
+    pub fn serialize<S: Serializer>(
+        name: &Option<XorName>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match name {
+            Some(name) if serializer.is_human_readable() => serializer.serialize_some(&Hex(*name)),
+            Some(name) => serializer.serialize_some(&Bytes(*name)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional name, in the representation used by `serialize`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<XorName>, D::Error> {
+        if deserializer.is_human_readable() {
+            Ok(Option::<Hex>::deserialize(deserializer)?.map(|hex| hex.0))
+        } else {
+            Ok(Option::<Bytes>::deserialize(deserializer)?.map(|bytes| bytes.0))
+        }
+    }
+}
+
+/// Serializes a `Vec<XorName>` as a sequence of hex strings in human readable formats, and as a
+/// single byte string of the concatenated names otherwise.
+pub mod vec {
+    use super::*;
+
+    /// Serializes `names` as hex strings or packed bytes.
+    pub fn serialize<S: Serializer>(names: &[XorName], serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_names(names, serializer)
+    }
+
+    /// Deserializes names, in the representation used by `serialize`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<XorName>, D::Error> {
+        deserialize_names(deserializer)
+    }
+}
+
+/// Serializes a `BTreeSet<XorName>` as a sequence of hex strings in human readable formats, and
+/// as a single byte string of the concatenated names otherwise. The names are in ascending
+/// order either way.
+pub mod btree_set {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Serializes `names` as hex strings or packed bytes.
+    pub fn serialize<S: Serializer>(
+        names: &BTreeSet<XorName>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_names(names, serializer)
+    }
+
+    /// Deserializes names, in the representation used by `serialize`. Duplicates are merged.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeSet<XorName>, D::Error> {
+        deserialize_names(deserializer)
+    }
+}
+
+fn serialize_names<'a, I, S>(names: I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    I: IntoIterator<Item = &'a XorName>,
+    I::IntoIter: ExactSizeIterator,
+    S: Serializer,
+{
+    let names = names.into_iter();
+    if serializer.is_human_readable() {
+        serializer.collect_seq(names.map(|name| Hex(*name)))
+    } else {
+        let mut packed = Vec::with_capacity(names.len() * XOR_NAME_LEN);
+        for name in names {
+            packed.extend_from_slice(&name.0);
+        }
+        serializer.serialize_bytes(&packed)
+    }
+}
+
+fn deserialize_names<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: FromIterator<XorName>,
+{
+    if deserializer.is_human_readable() {
+        let names = Vec::<Hex>::deserialize(deserializer)?;
+        Ok(names.into_iter().map(|hex| hex.0).collect())
+    } else {
+        let packed = deserializer.deserialize_bytes(PackedVisitor)?;
+        Ok(packed
+            .chunks_exact(XOR_NAME_LEN)
+            .map(|bytes| {
+                let mut name = XorName::default();
+                name.0.copy_from_slice(bytes);
+                name
+            })
+            .collect())
+    }
+}
+
+// A name in the `hex` representation.
+struct Hex(XorName);
+
+impl Serialize for Hex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        hex::deserialize(deserializer).map(Hex)
+    }
+}
+
+// A name in the `bytes` representation.
+struct Bytes(XorName);
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        bytes::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        bytes::deserialize(deserializer).map(Bytes)
+    }
+}
+
+pub(crate) struct HexVisitor;
+
+impl<'de> Visitor<'de> for HexVisitor {
+    type Value = XorName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} character hex string", 2 * XOR_NAME_LEN)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        s.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(s), &self))
+    }
+}
+
+pub(crate) struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = XorName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} bytes", XOR_NAME_LEN)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        XorName::try_from(bytes).map_err(|_| E::invalid_length(bytes.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut name = XorName::default();
+        for (i, byte) in name.0.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(XOR_NAME_LEN + 1, &self));
+        }
+        Ok(name)
+    }
+}
+
+// Accepts any number of whole names, as one byte string or as a sequence of numbers.
+struct PackedVisitor;
+
+impl<'de> Visitor<'de> for PackedVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a multiple of {} bytes", XOR_NAME_LEN)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        self.visit_byte_buf(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+        if bytes.len() % XOR_NAME_LEN != 0 {
+            return Err(E::invalid_length(bytes.len(), &self));
+        }
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // The length comes from the payload, so only trust it as far as a modest preallocation.
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_byte_buf(bytes)
+    }
+}
+
+#[cfg(all(test, feature = "serialize-hex"))]
+mod tests {
+    use super::{bytes, hex, *};
+    use serde::{
+        de::{
+            value::{BytesDeserializer, Error, SeqDeserializer, StrDeserializer, U8Deserializer},
+            Error as _,
+        },
+        Deserialize, Serialize,
+    };
+    use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Token};
+    use std::collections::BTreeSet;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "super::hex")]
+        hex: XorName,
+        #[serde(with = "super::bytes")]
+        bytes: XorName,
+    }
+
+    #[test]
+    fn representation_is_independent_of_format() {
+        let record = Record {
+            hex: XorName([0xAB; XOR_NAME_LEN]),
+            bytes: XorName([0xCD; XOR_NAME_LEN]),
+        };
+        let tokens = [
+            Token::Struct {
+                name: "Record",
+                len: 2,
+            },
+            Token::Str("hex"),
+            Token::Str("abababababababababababababababababababababababababababababababab"),
+            Token::Str("bytes"),
+            Token::Bytes(&[0xCD; XOR_NAME_LEN]),
+            Token::StructEnd,
+        ];
+
+        assert_tokens(&record.clone().readable(), &tokens);
+        assert_tokens(&record.compact(), &tokens);
+    }
+
+    #[test]
+    fn deserialize_alternative_inputs() {
+        let name = XorName([0xAB; XOR_NAME_LEN]);
+
+        let upper = "AB".repeat(XOR_NAME_LEN);
+        assert_eq!(hex::deserialize(str_de(&upper)), Ok(name));
+
+        // Bytes as a sequence of numbers, as produced by formats without byte strings.
+        let seq = SeqDeserializer::<_, Error>::new(name.0.iter().copied());
+        assert_eq!(bytes::deserialize(seq), Ok(name));
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        assert!(hex::deserialize(str_de("ab")).is_err());
+        assert!(hex::deserialize(str_de(&"xy".repeat(XOR_NAME_LEN))).is_err());
+        assert!(hex::deserialize(str_de(&"é".repeat(XOR_NAME_LEN))).is_err());
+        assert_eq!(
+            hex::deserialize(str_de("abcd")),
+            Err(Error::custom(
+                "invalid value: string \"abcd\", expected 64 character hex string"
+            ))
+        );
+
+        assert_eq!(
+            bytes::deserialize(BytesDeserializer::<Error>::new(&[0; 31])),
+            Err(Error::custom("invalid length 31, expected 32 bytes"))
+        );
+        let seq = SeqDeserializer::<_, Error>::new(vec![0u8; 33].into_iter());
+        assert_eq!(
+            bytes::deserialize(seq),
+            Err(Error::custom("invalid length 33, expected 32 bytes"))
+        );
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Collections {
+        #[serde(with = "super::option")]
+        option: Option<XorName>,
+        #[serde(with = "super::vec")]
+        vec: Vec<XorName>,
+        #[serde(with = "super::btree_set")]
+        set: BTreeSet<XorName>,
+    }
+
+    #[test]
+    fn collections() {
+        let collections = Collections {
+            option: Some(XorName([0xAB; XOR_NAME_LEN])),
+            vec: vec![XorName([0xAB; XOR_NAME_LEN]); 2],
+            set: vec![XorName([0xCD; XOR_NAME_LEN])].into_iter().collect(),
+        };
+        let hex_ab = "abababababababababababababababababababababababababababababababab";
+        let hex_cd = "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd";
+
+        assert_tokens(
+            &collections.clone().readable(),
+            &[
+                Token::Struct {
+                    name: "Collections",
+                    len: 3,
+                },
+                Token::Str("option"),
+                Token::Some,
+                Token::Str(hex_ab),
+                Token::Str("vec"),
+                Token::Seq { len: Some(2) },
+                Token::Str(hex_ab),
+                Token::Str(hex_ab),
+                Token::SeqEnd,
+                Token::Str("set"),
+                Token::Seq { len: Some(1) },
+                Token::Str(hex_cd),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &collections.compact(),
+            &[
+                Token::Struct {
+                    name: "Collections",
+                    len: 3,
+                },
+                Token::Str("option"),
+                Token::Some,
+                Token::Bytes(&[0xAB; XOR_NAME_LEN]),
+                Token::Str("vec"),
+                Token::Bytes(&[0xAB; 2 * XOR_NAME_LEN]),
+                Token::Str("set"),
+                Token::Bytes(&[0xCD; XOR_NAME_LEN]),
+                Token::StructEnd,
+            ],
+        );
+
+        let empty = Collections {
+            option: None,
+            vec: vec![],
+            set: BTreeSet::new(),
+        };
+        assert_tokens(
+            &empty.compact(),
+            &[
+                Token::Struct {
+                    name: "Collections",
+                    len: 3,
+                },
+                Token::Str("option"),
+                Token::None,
+                Token::Str("vec"),
+                Token::Bytes(&[]),
+                Token::Str("set"),
+                Token::Bytes(&[]),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn collections_reject_partial_names() {
+        assert_de_tokens_error::<Compact<Collections>>(
+            &[
+                Token::Struct {
+                    name: "Collections",
+                    len: 3,
+                },
+                Token::Str("option"),
+                Token::None,
+                Token::Str("vec"),
+                Token::Bytes(&[0; XOR_NAME_LEN + 1]),
+            ],
+            "invalid length 33, expected a multiple of 32 bytes",
+        );
+    }
+
+    #[test]
+    fn packed_ignores_hostile_size_hint() {
+        // Claims far more elements than could ever be allocated, but yields only one name.
+        struct Lying(core::ops::Range<usize>);
+
+        impl<'de> SeqAccess<'de> for Lying {
+            type Error = Error;
+
+            fn next_element_seed<T: de::DeserializeSeed<'de>>(
+                &mut self,
+                seed: T,
+            ) -> Result<Option<T::Value>, Error> {
+                self.0
+                    .next()
+                    .map(|_| seed.deserialize(U8Deserializer::new(0xAB)))
+                    .transpose()
+            }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(usize::MAX)
+            }
+        }
+
+        assert_eq!(
+            PackedVisitor.visit_seq(Lying(0..XOR_NAME_LEN)),
+            Ok(vec![0xAB; XOR_NAME_LEN])
+        );
+    }
+
+    fn str_de(s: &str) -> StrDeserializer<'_, Error> {
+        StrDeserializer::new(s)
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{Prefix, XorName};
+use crate::{
+    prefix::PrefixFields,
+    serde_helpers::{BytesVisitor, HexVisitor},
+    Prefix, XorName, XOR_NAME_LEN,
+};
 use serde::{
-    de::{self, Visitor},
+    de::{self, SeqAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -26,32 +30,46 @@ impl<'de> Deserialize<'de> for XorName {
     where
         D: Deserializer<'de>,
     {
+        // Self-describing formats can hold the name in any of the representations produced by
+        // other serializers, so accept all of them rather than trusting `is_human_readable`.
         if deserializer.is_human_readable() {
-            struct XorNameHexStrVisitor;
-            impl<'de> Visitor<'de> for XorNameHexStrVisitor {
-                type Value = XorName;
+            return deserializer.deserialize_any(XorNameVisitor);
+        }
+        deserializer.deserialize_newtype_struct("XorName", XorNameVisitor)
+    }
+}
 
-                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                    write!(formatter, "32 byte hex string")
-                }
+// Accepts a hex string, a byte string or a sequence of bytes, optionally in a newtype struct.
+struct XorNameVisitor;
 
-                fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
-                where
-                    E: de::Error,
-                {
-                    let buffer = <[u8; 32] as hex::FromHex>::from_hex(s)
-                        .map_err(|e| E::custom(std::format!("hex decoding ({})", e)))?;
-                    Ok(XorName(buffer))
-                }
-            }
-            return deserializer.deserialize_str(XorNameHexStrVisitor);
-        }
+impl<'de> Visitor<'de> for XorNameVisitor {
+    type Value = XorName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "32 byte hex string or 32 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        HexVisitor.visit_str(s)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        BytesVisitor.visit_bytes(bytes)
+    }
 
-        #[derive(Deserialize)]
-        #[serde(rename = "XorName")]
-        struct XorNameDerived([u8; 32]);
-        let x = <XorNameDerived as Deserialize>::deserialize(deserializer)?;
-        Ok(XorName(x.0))
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        BytesVisitor.visit_seq(seq)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_tuple(XOR_NAME_LEN, self)
+        }
     }
 }
 
@@ -97,17 +115,9 @@ impl<'de> Deserialize<'de> for Prefix {
             return deserializer.deserialize_str(PrefixVisitor);
         }
 
-        #[derive(Deserialize)]
-        #[serde(rename = "Prefix")]
-        struct PrefixDerived {
-            bit_count: u16,
-            name: XorName,
-        }
-        let p = <PrefixDerived as Deserialize>::deserialize(deserializer)?;
-        Ok(Prefix {
-            bit_count: p.bit_count,
-            name: p.name,
-        })
+        PrefixFields::deserialize(deserializer)?
+            .validate()
+            .map_err(de::Error::custom)
     }
 }
 
@@ -140,6 +150,51 @@ mod test {
         assert_tokens(&xor_derived.compact(), &xor_tokens("XorNameDerived"));
     }
 
+    #[test]
+    fn xorname_de_alternative_representations() {
+        let xor = XorName([0xAA; 32]);
+        let hex_str = static_str("aa".repeat(32));
+        let mut seq = vec![Token::Seq { len: Some(32) }];
+        seq.extend_from_slice(&[Token::U8(0xAA); 32]);
+        seq.push(Token::SeqEnd);
+
+        for tokens in [
+            vec![Token::Bytes(&[0xAA; 32])],
+            vec![Token::ByteBuf(&[0xAA; 32])],
+            seq.clone(),
+            vec![
+                Token::NewtypeStruct { name: "XorName" },
+                Token::Str(hex_str),
+            ],
+        ] {
+            assert_de_tokens(&xor.readable(), &tokens);
+        }
+
+        for tokens in [
+            vec![Token::Bytes(&[0xAA; 32])],
+            vec![Token::Str(hex_str)],
+            seq,
+        ] {
+            assert_de_tokens(&xor.compact(), &tokens);
+        }
+    }
+
+    #[test]
+    fn xorname_de_rejects_wrong_length() {
+        assert_de_tokens_error::<Readable<XorName>>(
+            &[Token::Bytes(&[0xAA; 31])],
+            "invalid length 31, expected 32 bytes",
+        );
+        assert_de_tokens_error::<Compact<XorName>>(
+            &[Token::Str("aaaa")],
+            "invalid value: string \"aaaa\", expected 64 character hex string",
+        );
+        assert_de_tokens_error::<Readable<XorName>>(
+            &[Token::Seq { len: Some(2) }, Token::U8(0xAA), Token::SeqEnd],
+            "invalid length 1, expected 32 bytes",
+        );
+    }
+
     #[test]
     fn prefix_ser_de() {
         let bit_count = 15;
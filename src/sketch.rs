@@ -0,0 +1,271 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, Prefix, XorName};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A Bloom filter over the names matching a prefix.
+///
+/// It answers whether a name may have been inserted, with false positives but no false negatives,
+/// using a fixed amount of memory however many names are inserted. This makes it a cheap
+/// pre-filter when reconciling large member lists: peers exchange sketches of their sections,
+/// and only the names the other side's sketch doesn't contain need to be exchanged exactly.
+///
+/// Sketches can be serialized to be sent to other peers, and merged with sketches of the same
+/// prefix and shape. Deserialization rejects shapes outside the limits that `new` and
+/// `with_shape` keep to, so that a peer can't make lookups arbitrarily expensive.
+///
+/// Like [`XorNameHasher`](crate::XorNameHasher), the sketch uses the bits of the names directly
+/// instead of hashing them again, here their last 16 bytes. That is only suitable for uniformly
+/// random names, not for names an attacker can choose.
+///
+/// ```
+/// use xor_name::{xor_name, NameSketch};
+///
+/// let prefix = "0".parse().unwrap();
+/// let mut sketch = NameSketch::new(prefix, 100, 0.01);
+/// let name = xor_name!(0b0110_0000, 1, 2, 3);
+/// assert!(sketch.insert(&name));
+/// assert!(sketch.maybe_contains(&name));
+/// // Names not matching the prefix are never contained.
+/// assert!(!sketch.insert(&xor_name!(0b1000_0000)));
+/// assert!(!sketch.maybe_contains(&xor_name!(0b1000_0000)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NameSketch {
+    prefix: Prefix,
+    hash_count: u32,
+    bits: Vec<u64>,
+}
+
+impl NameSketch {
+    /// The largest number of 64-bit words of storage, i. e. 8 MiB.
+    pub const MAX_WORDS: usize = 1 << 20;
+    /// The largest number of bits set per name.
+    pub const MAX_HASH_COUNT: u32 = 32;
+
+    /// Creates an empty sketch for the names matching `prefix`, sized so that after
+    /// `expected_names` insertions, a name that wasn't inserted is reported as contained with
+    /// a probability of about `false_positive_rate`.
+    ///
+    /// The rate is clamped between `1e-9` and `0.5`, and `NaN` is taken as `1e-9`. The size is
+    /// capped at [`MAX_WORDS`](Self::MAX_WORDS), so the rate is higher for very large numbers of
+    /// names.
+    pub fn new(prefix: Prefix, expected_names: usize, false_positive_rate: f64) -> Self {
+        let names = expected_names.max(1) as f64;
+        let rate = if false_positive_rate.is_nan() {
+            1e-9
+        } else {
+            false_positive_rate.clamp(1e-9, 0.5)
+        };
+        // The optimal number of bits and of hash functions of a Bloom filter.
+        let bit_len = (-names * rate.ln() / (2f64.ln() * 2f64.ln())).ceil();
+        let words = (bit_len / 64.0).ceil().clamp(1.0, Self::MAX_WORDS as f64) as usize;
+        let hash_count = ((64 * words) as f64 / names * 2f64.ln()).round();
+        Self::with_shape(prefix, words, hash_count as u32)
+    }
+
+    /// Creates an empty sketch for the names matching `prefix`, with `words` 64-bit words of
+    /// storage and `hash_count` bits set per name.
+    ///
+    /// `words` is clamped between `1` and [`MAX_WORDS`](Self::MAX_WORDS), and `hash_count`
+    /// between `1` and [`MAX_HASH_COUNT`](Self::MAX_HASH_COUNT).
+    pub fn with_shape(prefix: Prefix, words: usize, hash_count: u32) -> Self {
+        Self {
+            prefix,
+            hash_count: hash_count.clamp(1, Self::MAX_HASH_COUNT),
+            bits: vec![0; words.clamp(1, Self::MAX_WORDS)],
+        }
+    }
+
+    /// Returns the prefix whose names the sketch holds.
+    pub fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Adds `name` to the sketch. Returns `false`, leaving the sketch unchanged, if `name`
+    /// doesn't match its prefix.
+    pub fn insert(&mut self, name: &XorName) -> bool {
+        if !self.prefix.matches(name) {
+            return false;
+        }
+        for bit in self.bit_indices(name) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+        true
+    }
+
+    /// Returns `false` if `name` was definitely not inserted, and `true` if it may have been.
+    pub fn maybe_contains(&self, name: &XorName) -> bool {
+        self.prefix.matches(name)
+            && self
+                .bit_indices(name)
+                .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Adds all names of `other` to `self`. Fails with [`Error::IncompatibleSketch`] if the two
+    /// sketches differ in prefix, size or number of hash functions.
+    pub fn merge(&mut self, other: &Self) -> Result<(), Error> {
+        if self.prefix != other.prefix
+            || self.hash_count != other.hash_count
+            || self.bits.len() != other.bits.len()
+        {
+            return Err(Error::IncompatibleSketch);
+        }
+        self.bits
+            .iter_mut()
+            .zip(&other.bits)
+            .for_each(|(lhs, rhs)| *lhs |= rhs);
+        Ok(())
+    }
+
+    /// Returns the bits to set for `name`, using double hashing with two words of the name.
+    fn bit_indices<'a>(&self, name: &'a XorName) -> impl Iterator<Item = usize> + 'a {
+        let bit_len = 64 * self.bits.len() as u64;
+        let (first, second) = (name.word(3), name.word(2) | 1);
+        (0..self.hash_count as u64)
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % bit_len) as usize)
+    }
+}
+
+// The fields of a `NameSketch` as they appear in the serialized form, before validation.
+#[derive(Deserialize)]
+#[serde(rename = "NameSketch")]
+struct SketchFields {
+    prefix: Prefix,
+    hash_count: u32,
+    bits: Vec<u64>,
+}
+
+impl SketchFields {
+    fn validate(self) -> Result<NameSketch, Error> {
+        if !(1..=NameSketch::MAX_HASH_COUNT).contains(&self.hash_count)
+            || !(1..=NameSketch::MAX_WORDS).contains(&self.bits.len())
+        {
+            return Err(Error::InvalidSketch);
+        }
+        Ok(NameSketch {
+            prefix: self.prefix,
+            hash_count: self.hash_count,
+            bits: self.bits,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for NameSketch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SketchFields::deserialize(deserializer)?
+            .validate()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn no_false_negatives() {
+        let mut rng = SmallRng::from_entropy();
+        let prefix = Prefix::new(3, rng.gen());
+        let names: Vec<XorName> = (0..1000)
+            .map(|_| prefix.substituted_in(rng.gen()))
+            .collect();
+        let mut sketch = NameSketch::new(prefix, names.len(), 0.01);
+        for name in &names {
+            assert!(sketch.insert(name));
+        }
+        assert!(names.iter().all(|name| sketch.maybe_contains(name)));
+
+        // About 1% false positives; 3% is exceeded with negligible probability.
+        let false_positives = (0..10_000)
+            .map(|_| prefix.substituted_in(rng.gen()))
+            .filter(|name| sketch.maybe_contains(name))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+
+        let outside = prefix.sibling().substituted_in(rng.gen());
+        assert!(!sketch.insert(&outside));
+        assert!(!sketch.maybe_contains(&outside));
+    }
+
+    #[test]
+    fn merge() {
+        let mut rng = SmallRng::from_entropy();
+        let prefix = Prefix::default();
+        let (lhs_names, rhs_names): (Vec<XorName>, Vec<XorName>) = (0..100)
+            .map(|_| (rng.gen::<XorName>(), rng.gen::<XorName>()))
+            .unzip();
+
+        let mut lhs = NameSketch::new(prefix, 200, 0.01);
+        let mut rhs = lhs.clone();
+        lhs_names.iter().for_each(|name| assert!(lhs.insert(name)));
+        rhs_names.iter().for_each(|name| assert!(rhs.insert(name)));
+
+        assert_eq!(lhs.merge(&rhs), Ok(()));
+        assert!(lhs_names
+            .iter()
+            .chain(&rhs_names)
+            .all(|name| lhs.maybe_contains(name)));
+
+        let other_prefix = NameSketch::new(prefix.pushed(true), 200, 0.01);
+        assert_eq!(lhs.merge(&other_prefix), Err(Error::IncompatibleSketch));
+        let other_size = NameSketch::new(prefix, 2000, 0.01);
+        assert_eq!(lhs.merge(&other_size), Err(Error::IncompatibleSketch));
+    }
+
+    #[test]
+    fn degenerate_shapes() {
+        let name: XorName = SmallRng::from_entropy().gen();
+        // Sketches always have storage and at least one hash function.
+        for (words, hash_count) in [(0, 4), (4, 0)] {
+            let mut sketch = NameSketch::with_shape(Prefix::default(), words, hash_count);
+            assert!(!sketch.bits.is_empty());
+            assert!(sketch.hash_count > 0);
+            assert!(!sketch.maybe_contains(&name));
+            assert!(sketch.insert(&name));
+            assert!(sketch.maybe_contains(&name));
+        }
+        let sketch = NameSketch::with_shape(Prefix::default(), usize::MAX, u32::MAX);
+        assert_eq!(sketch.bits.len(), NameSketch::MAX_WORDS);
+        assert_eq!(sketch.hash_count, NameSketch::MAX_HASH_COUNT);
+
+        let sketch = NameSketch::new(Prefix::default(), 0, f64::NAN);
+        assert!(!sketch.maybe_contains(&name));
+    }
+
+    #[test]
+    fn serialisation() {
+        let mut rng = SmallRng::from_entropy();
+        let mut sketch = NameSketch::new(Prefix::new(5, rng.gen()), 10, 0.1);
+        let name = sketch.prefix().substituted_in(rng.gen());
+        assert!(sketch.insert(&name));
+
+        let bytes = bincode::serialize(&sketch).unwrap();
+        let deserialized: NameSketch = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(deserialized, sketch);
+        assert!(deserialized.maybe_contains(&name));
+    }
+
+    #[test]
+    fn hostile_serialisation() {
+        let valid = NameSketch::with_shape(Prefix::default(), 4, 3);
+        let hostile = |hash_count: u32, words: usize| {
+            let fields = (valid.prefix, hash_count, vec![0u64; words]);
+            bincode::deserialize::<NameSketch>(&bincode::serialize(&fields).unwrap())
+        };
+        assert_eq!(hostile(3, 4).unwrap(), valid);
+        assert!(hostile(u32::MAX, 4).is_err());
+        assert!(hostile(NameSketch::MAX_HASH_COUNT + 1, 4).is_err());
+        assert!(hostile(0, 4).is_err());
+        assert!(hostile(3, 0).is_err());
+        assert!(hostile(3, NameSketch::MAX_WORDS + 1).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Prefix, XorName, XOR_NAME_LEN};
+
+/// The outcome of [`recommend_split`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SplitAdvice {
+    /// The section has no more than the maximum number of members and should stay as it is.
+    Keep,
+    /// The section is too large, but one of its children would have fewer than the minimum
+    /// number of members, so it can't split yet. Contains both children with their member
+    /// counts.
+    Unbalanced([(Prefix, usize); 2]),
+    /// The section should split into these disjoint descendants, in order, with their member
+    /// counts. Together they cover the whole section.
+    Split(Vec<(Prefix, usize)>),
+}
+
+/// Recommends whether and how the section with the given `prefix` should split, given the names
+/// of its `members` and the minimum and maximum section sizes. Names not matching `prefix` are
+/// ignored.
+///
+/// A section with more than `max_size` members splits into its two children if each of them has
+/// at least `min_size` members. The children are split further in the same way, so a section
+/// that has grown well beyond `max_size` can be split several levels deep at once, and a child
+/// that is too small to split again is left as it is.
+///
+/// ```
+/// use xor_name::{recommend_split, xor_name, SplitAdvice};
+///
+/// let members = [xor_name!(0b0000_0000), xor_name!(0b0100_0000), xor_name!(0b1000_0000)];
+/// let prefix = "".parse().unwrap();
+/// match recommend_split(&prefix, &members, 1, 2) {
+///     SplitAdvice::Split(sections) => {
+///         assert_eq!(sections, [("0".parse().unwrap(), 2), ("1".parse().unwrap(), 1)]);
+///     }
+///     advice => panic!("unexpected advice: {:?}", advice),
+/// }
+/// assert_eq!(recommend_split(&prefix, &members, 2, 2), SplitAdvice::Unbalanced([
+///     ("0".parse().unwrap(), 2),
+///     ("1".parse().unwrap(), 1),
+/// ]));
+/// assert_eq!(recommend_split(&prefix, &members, 1, 3), SplitAdvice::Keep);
+/// ```
+pub fn recommend_split<'a, I>(
+    prefix: &Prefix,
+    members: I,
+    min_size: usize,
+    max_size: usize,
+) -> SplitAdvice
+where
+    I: IntoIterator<Item = &'a XorName>,
+{
+    let mut names: Vec<XorName> = members
+        .into_iter()
+        .filter(|name| prefix.matches(name))
+        .copied()
+        .collect();
+    names.sort_unstable();
+    let count = |prefix: &Prefix| {
+        let start = names.partition_point(|name| *name < prefix.lower_bound());
+        let end = names.partition_point(|name| *name <= prefix.upper_bound());
+        end - start
+    };
+
+    // The children of `prefix` with their sizes, if it should split into them.
+    let split = |prefix: &Prefix, size: usize| {
+        if size <= max_size || prefix.bit_count() == 8 * XOR_NAME_LEN {
+            return None;
+        }
+        let children =
+            [prefix.pushed(false), prefix.pushed(true)].map(|child| (child, count(&child)));
+        Some(children)
+    };
+
+    let children = match split(prefix, names.len()) {
+        None => return SplitAdvice::Keep,
+        Some(children) if children.iter().any(|(_, size)| *size < min_size) => {
+            return SplitAdvice::Unbalanced(children)
+        }
+        Some(children) => children,
+    };
+
+    // Depth-first, so the sections come out in order.
+    let mut sections = Vec::new();
+    let mut pending: Vec<_> = children.iter().rev().copied().collect();
+    while let Some((prefix, size)) = pending.pop() {
+        match split(&prefix, size) {
+            Some(children) if children.iter().all(|(_, size)| *size >= min_size) => {
+                pending.extend(children.iter().rev().copied())
+            }
+            _ => sections.push((prefix, size)),
+        }
+    }
+    SplitAdvice::Split(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    #[test]
+    fn recommend_split() {
+        let parse = |bits: &str| bits.parse::<Prefix>().unwrap();
+        let members: Vec<XorName> = [
+            0b0000_0000,
+            0b0010_0000,
+            0b0100_0000,
+            0b0110_0000,
+            0b1000_0000,
+        ]
+        .iter()
+        .map(|byte| XorName([*byte; XOR_NAME_LEN]))
+        .collect();
+
+        assert_eq!(
+            super::recommend_split(&parse(""), &members, 1, 5),
+            SplitAdvice::Keep
+        );
+        assert_eq!(
+            super::recommend_split(&parse(""), &members[..0], 0, 0),
+            SplitAdvice::Keep
+        );
+        assert_eq!(
+            super::recommend_split(&parse(""), &members, 2, 4),
+            SplitAdvice::Unbalanced([(parse("0"), 4), (parse("1"), 1)])
+        );
+        // `1` is too small to split, but `0` splits again into two sections of 2.
+        assert_eq!(
+            super::recommend_split(&parse(""), &members, 1, 2),
+            SplitAdvice::Split(vec![(parse("00"), 2), (parse("01"), 2), (parse("1"), 1)])
+        );
+        assert_eq!(
+            super::recommend_split(&parse(""), &members, 0, 1),
+            SplitAdvice::Split(vec![
+                (parse("000"), 1),
+                (parse("001"), 1),
+                (parse("010"), 1),
+                (parse("011"), 1),
+                (parse("1"), 1),
+            ])
+        );
+        // Only the members under the prefix count.
+        assert_eq!(
+            super::recommend_split(&parse("0"), &members, 2, 3),
+            SplitAdvice::Split(vec![(parse("00"), 2), (parse("01"), 2)])
+        );
+
+        // Identical names can never be separated.
+        let same = [members[0]; 3];
+        assert_eq!(
+            super::recommend_split(&parse(""), &same, 1, 2),
+            SplitAdvice::Unbalanced([(parse("0"), 3), (parse("1"), 0)])
+        );
+        // Without a minimum size, they end up in a full-length prefix.
+        let mut expected = vec![(Prefix::from(members[0]), 3)];
+        expected.extend((1..=8 * XOR_NAME_LEN).rev().map(|len| {
+            let prefix = Prefix::new(len, members[0]);
+            (prefix.with_flipped_bit((len - 1) as u8), 0)
+        }));
+        assert_eq!(
+            super::recommend_split(&parse(""), &same, 0, 2),
+            SplitAdvice::Split(expected)
+        );
+    }
+
+    #[test]
+    fn split_sections_cover_prefix() {
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..20 {
+            let prefix = Prefix::new(rng.gen_range(0..4), rng.gen());
+            let members: Vec<XorName> = (0..rng.gen_range(0..200))
+                .map(|_| prefix.substituted_in(rng.gen()))
+                .collect();
+            let (min_size, max_size) = (rng.gen_range(0..10), rng.gen_range(10..30));
+
+            match super::recommend_split(&prefix, &members, min_size, max_size) {
+                SplitAdvice::Keep => assert!(members.len() <= max_size),
+                SplitAdvice::Unbalanced(children) => {
+                    assert!(members.len() > max_size);
+                    assert!(children.iter().any(|(_, size)| *size < min_size));
+                }
+                SplitAdvice::Split(sections) => {
+                    let prefixes: Vec<_> = sections.iter().map(|(prefix, _)| *prefix).collect();
+                    assert!(prefix.is_covered_by(&prefixes));
+                    assert!(prefixes
+                        .windows(2)
+                        .all(|pair| pair[0] < pair[1] && !pair[0].is_compatible(&pair[1])));
+                    for (section, size) in sections {
+                        assert!(section.is_extension_of(&prefix));
+                        assert!(size >= min_size);
+                        assert_eq!(
+                            size,
+                            members.iter().filter(|name| section.matches(name)).count()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Fixtures and assertions for testing code built on names and prefixes.
+//!
+//! The fixtures are derived from a seed by hashing, so the same seed gives the same names and
+//! prefixes on every platform and with every version of `rand`.
+//!
+//! ```
+//! use xor_name::{prefix, test_utils};
+//!
+//! let parent = prefix!("01");
+//! let sections = test_utils::partition(&parent, 7, 5);
+//! test_utils::assert_partition(&parent, &sections);
+//!
+//! let names = test_utils::names_matching(&sections[0], 7, 10);
+//! assert!(names.iter().all(|name| sections[0].matches(name)));
+//! ```
+
+use crate::{Prefix, XorName, XOR_NAME_LEN};
+
+/// Returns the name derived from `seed`.
+pub fn name(seed: u64) -> XorName {
+    XorName::from_content_parts(&[b"xor_name::test_utils", &seed.to_be_bytes()])
+}
+
+/// Returns `count` distinct names derived from `seed`.
+pub fn names(seed: u64, count: usize) -> Vec<XorName> {
+    (0..count as u64)
+        .map(|i| XorName::from_content_parts(&[&name(seed).0, &i.to_be_bytes()]))
+        .collect()
+}
+
+/// Returns `count` names matching `prefix`, derived from `seed`.
+pub fn names_matching(prefix: &Prefix, seed: u64, count: usize) -> Vec<XorName> {
+    names(seed, count)
+        .into_iter()
+        .map(|name| prefix.substituted_in(name))
+        .collect()
+}
+
+/// Returns `count` disjoint prefixes covering `parent`, in order, derived from `seed`.
+///
+/// Starting from `parent` alone, a prefix chosen by `seed` is repeatedly replaced by its two
+/// children, so the result has prefixes of varying lengths like a real network. Returns fewer
+/// prefixes only if `count` is `0` or the prefixes can't be split any further.
+pub fn partition(parent: &Prefix, seed: u64, count: usize) -> Vec<Prefix> {
+    let mut prefixes = vec![*parent];
+    for choice in names(seed, count.saturating_sub(1)) {
+        let splittable: Vec<usize> = (0..prefixes.len())
+            .filter(|i| prefixes[*i].bit_count() < 8 * XOR_NAME_LEN)
+            .collect();
+        if splittable.is_empty() {
+            break;
+        }
+        let index = splittable[choice.fold_to_u64() as usize % splittable.len()];
+        let prefix = prefixes[index];
+        prefixes[index] = prefix.pushed(false);
+        prefixes.insert(index + 1, prefix.pushed(true));
+    }
+    prefixes.truncate(count);
+    prefixes
+}
+
+/// Asserts that `prefixes` are disjoint, in order, and together cover exactly `parent`.
+///
+/// # Panics
+///
+/// Panics with a description of the first violation found.
+pub fn assert_partition(parent: &Prefix, prefixes: &[Prefix]) {
+    for prefix in prefixes {
+        assert!(
+            prefix.is_extension_of(parent) || prefix == parent,
+            "{:?} is not within {:?}",
+            prefix,
+            parent
+        );
+    }
+    for pair in prefixes.windows(2) {
+        assert!(
+            !pair[0].is_compatible(&pair[1]),
+            "{:?} and {:?} overlap",
+            pair[0],
+            pair[1]
+        );
+        assert!(
+            pair[0] < pair[1],
+            "{:?} and {:?} are out of order",
+            pair[0],
+            pair[1]
+        );
+    }
+    assert!(
+        parent.is_covered_by(prefixes),
+        "{:?} is not covered by {:?}",
+        parent,
+        prefixes
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(name(1), name(1));
+        assert_ne!(name(1), name(2));
+        assert_eq!(names(3, 10), names(3, 10));
+        assert_eq!(names(3, 10)[..4], names(3, 4)[..]);
+        assert_eq!(partition(&Prefix::default(), 4, 20).len(), 20);
+        assert_eq!(
+            partition(&Prefix::default(), 4, 20),
+            partition(&Prefix::default(), 4, 20)
+        );
+    }
+
+    #[test]
+    fn partitions() {
+        let parent: Prefix = "101".parse().unwrap();
+        for seed in 0..20 {
+            assert_partition(&parent, &partition(&parent, seed, seed as usize + 1));
+        }
+        assert!(partition(&parent, 0, 0).is_empty());
+
+        let full = Prefix::new(8 * XOR_NAME_LEN, name(0));
+        assert_eq!(partition(&full, 0, 3), [full]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap")]
+    fn overlapping_partition() {
+        let parse = |bits: &str| bits.parse::<Prefix>().unwrap();
+        assert_partition(&parse(""), &[parse("0"), parse("01"), parse("1")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not covered")]
+    fn incomplete_partition() {
+        let parse = |bits: &str| bits.parse::<Prefix>().unwrap();
+        assert_partition(&parse(""), &[parse("0"), parse("10")]);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! `ufmt` implementations, producing the same output as the `core::fmt` ones.
+//!
+//! They avoid the code size of `core::fmt`, but the crate as a whole still requires `std`.
+
+use crate::{Prefix, XorName};
+use ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+fn write_hex<W>(formatter: &mut Formatter<'_, W>, bytes: &[u8]) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    for byte in bytes {
+        formatter.write_char(HEX_DIGITS[(byte >> 4) as usize] as char)?;
+        formatter.write_char(HEX_DIGITS[(byte & 0x0F) as usize] as char)?;
+    }
+    Ok(())
+}
+
+fn write_bits<W>(
+    formatter: &mut Formatter<'_, W>,
+    name: &XorName,
+    bit_count: usize,
+) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    for i in 0..bit_count {
+        formatter.write_char(if name.bit(i as u8) { '1' } else { '0' })?;
+    }
+    Ok(())
+}
+
+impl uDisplay for XorName {
+    fn fmt<W>(&self, formatter: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        write_hex(formatter, &self[..3])?;
+        formatter.write_str("..")
+    }
+}
+
+impl uDebug for XorName {
+    fn fmt<W>(&self, formatter: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        write_hex(formatter, &self[..3])?;
+        formatter.write_char('(')?;
+        write_bits(formatter, self, 8)?;
+        formatter.write_str(")..")
+    }
+}
+
+impl uDisplay for Prefix {
+    fn fmt<W>(&self, formatter: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        write_bits(formatter, &self.name, self.bit_count())
+    }
+}
+
+impl uDebug for Prefix {
+    fn fmt<W>(&self, formatter: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        formatter.write_str("Prefix(")?;
+        write_bits(formatter, &self.name, self.bit_count())?;
+        formatter.write_char(')')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    struct Buffer(String);
+
+    impl uWrite for Buffer {
+        type Error = Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    fn display<T: uDisplay>(value: &T) -> String {
+        let mut buffer = Buffer(String::new());
+        let _ = ufmt::uwrite!(buffer, "{}", value);
+        buffer.0
+    }
+
+    fn debug<T: uDebug>(value: &T) -> String {
+        let mut buffer = Buffer(String::new());
+        let _ = ufmt::uwrite!(buffer, "{:?}", value);
+        buffer.0
+    }
+
+    #[test]
+    fn same_as_core_fmt() {
+        let mut rng = SmallRng::from_entropy();
+        for _ in 0..20 {
+            let name: XorName = rng.gen();
+            assert_eq!(display(&name), std::format!("{}", name));
+            assert_eq!(debug(&name), std::format!("{:?}", name));
+
+            let prefix = Prefix::new(rng.gen_range(0..=256), name);
+            assert_eq!(display(&prefix), std::format!("{}", prefix));
+            assert_eq!(debug(&prefix), std::format!("{:?}", prefix));
+        }
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! JavaScript bindings for `XorName` and `Prefix`, enabled with the `wasm` feature.
+//!
+//! The exported classes are thin wrappers, so every operation has exactly the same semantics as
+//! on the Rust side.
+
+use crate::{Prefix, XorName, XOR_NAME_LEN};
+use core::{cmp::Ordering, convert::TryFrom, str::FromStr};
+use rand_core::OsRng;
+use wasm_bindgen::prelude::*;
+
+/// `XorName` as exposed to JavaScript.
+#[wasm_bindgen(js_name = XorName)]
+#[derive(Clone, Copy)]
+pub struct JsXorName(XorName);
+
+#[wasm_bindgen(js_class = XorName)]
+impl JsXorName {
+    /// Parses a name from a 64 character hex string.
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(hex: &str) -> Result<JsXorName, JsError> {
+        Ok(Self(XorName::from_str(hex)?))
+    }
+
+    /// Creates a name from exactly 32 bytes.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<JsXorName, JsError> {
+        Ok(Self(XorName::try_from(bytes)?))
+    }
+
+    /// Generates the name for the given content.
+    #[wasm_bindgen(js_name = fromContent)]
+    pub fn from_content(content: &[u8]) -> JsXorName {
+        Self(XorName::from_content(content))
+    }
+
+    /// Generates a random name using the platform's secure random source (`crypto.getRandomValues`
+    /// in browsers).
+    pub fn random() -> JsXorName {
+        Self(XorName::random(&mut OsRng))
+    }
+
+    /// Returns the 32 bytes of the name.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0 .0.to_vec()
+    }
+
+    /// Returns the full lowercase hex representation of the name.
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        std::format!("{:x}", self.0)
+    }
+
+    /// Returns the abbreviated representation also used by `Display` in Rust.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        std::format!("{}", self.0)
+    }
+
+    /// Returns the first `bitCount` bits of the name as a string of `0`s and `1`s.
+    #[wasm_bindgen(js_name = toBinary)]
+    pub fn to_binary(&self, bit_count: usize) -> String {
+        std::format!("{:1$b}", self.0, bit_count.min(8 * XOR_NAME_LEN))
+    }
+
+    /// Returns `true` if the `i`-th bit is `1`.
+    pub fn bit(&self, i: u8) -> bool {
+        self.0.bit(i)
+    }
+
+    /// Returns `true` if both names are equal.
+    pub fn equals(&self, other: &JsXorName) -> bool {
+        self.0 == other.0
+    }
+
+    /// Compares the distance of the arguments to `this`. Returns `-1` if `lhs` is closer, `1` if
+    /// `rhs` is closer and `0` if `lhs == rhs`.
+    #[wasm_bindgen(js_name = cmpDistance)]
+    pub fn cmp_distance(&self, lhs: &JsXorName, rhs: &JsXorName) -> i32 {
+        ordering_to_i32(self.0.cmp_distance(&lhs.0, &rhs.0))
+    }
+}
+
+/// `Prefix` as exposed to JavaScript.
+#[wasm_bindgen(js_name = Prefix)]
+#[derive(Clone, Copy)]
+pub struct JsPrefix(Prefix);
+
+#[wasm_bindgen(js_class = Prefix)]
+impl JsPrefix {
+    /// Creates a prefix with the first `bitCount` bits of `name`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(bit_count: usize, name: &JsXorName) -> JsPrefix {
+        Self(Prefix::new(bit_count, name.0))
+    }
+
+    /// Parses a prefix from a string of `0`s and `1`s.
+    pub fn parse(bits: &str) -> Result<JsPrefix, JsError> {
+        Prefix::from_str(bits)
+            .map(Self)
+            .map_err(|e| JsError::new(&std::format!("{}", e)))
+    }
+
+    /// Returns the number of bits in the prefix.
+    #[wasm_bindgen(getter, js_name = bitCount)]
+    pub fn bit_count(&self) -> usize {
+        self.0.bit_count()
+    }
+
+    /// Returns the name of this prefix.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> JsXorName {
+        JsXorName(self.0.name())
+    }
+
+    /// Returns `true` if this is a prefix of the given `name`.
+    pub fn matches(&self, name: &JsXorName) -> bool {
+        self.0.matches(&name.0)
+    }
+
+    /// Returns `true` if `this` is a prefix of `other` or vice versa.
+    #[wasm_bindgen(js_name = isCompatible)]
+    pub fn is_compatible(&self, other: &JsPrefix) -> bool {
+        self.0.is_compatible(&other.0)
+    }
+
+    /// Returns `true` if the `other` prefix differs in exactly one bit from this one.
+    #[wasm_bindgen(js_name = isNeighbour)]
+    pub fn is_neighbour(&self, other: &JsPrefix) -> bool {
+        self.0.is_neighbour(&other.0)
+    }
+
+    /// Compares the distance of `this` and `other` to `target`. Returns `-1` if `this` is closer,
+    /// `1` if `other` is closer, with ties broken deterministically.
+    #[wasm_bindgen(js_name = cmpDistance)]
+    pub fn cmp_distance(&self, other: &JsPrefix, target: &JsXorName) -> i32 {
+        ordering_to_i32(self.0.cmp_distance(&other.0, &target.0))
+    }
+
+    /// Returns `true` if both prefixes are equal.
+    pub fn equals(&self, other: &JsPrefix) -> bool {
+        self.0 == other.0
+    }
+
+    /// Returns the prefix extended by one bit.
+    pub fn pushed(&self, bit: bool) -> JsPrefix {
+        Self(self.0.pushed(bit))
+    }
+
+    /// Returns the prefix with its last bit removed.
+    pub fn popped(&self) -> JsPrefix {
+        Self(self.0.popped())
+    }
+
+    /// Returns the same prefix with the last bit flipped.
+    pub fn sibling(&self) -> JsPrefix {
+        Self(self.0.sibling())
+    }
+
+    /// Returns the smallest name matching the prefix.
+    #[wasm_bindgen(js_name = lowerBound)]
+    pub fn lower_bound(&self) -> JsXorName {
+        JsXorName(self.0.lower_bound())
+    }
+
+    /// Returns the largest name matching the prefix.
+    #[wasm_bindgen(js_name = upperBound)]
+    pub fn upper_bound(&self) -> JsXorName {
+        JsXorName(self.0.upper_bound())
+    }
+
+    /// Returns the prefix as a string of `0`s and `1`s.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        std::format!("{}", self.0)
+    }
+}
+
+fn ordering_to_i32(ordering: Ordering) -> i32 {
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
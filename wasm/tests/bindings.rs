@@ -0,0 +1,68 @@
+// Copyright 2026 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Runs the bindings in a JavaScript engine, e.g. with `wasm-pack test --node`.
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use xor_name_wasm::{JsPrefix as Prefix, JsXorName as XorName};
+
+const HEX: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+#[wasm_bindgen_test]
+fn hex_round_trip() {
+    let name = XorName::from_hex(HEX).unwrap();
+    assert_eq!(name.to_hex(), HEX);
+    assert!(XorName::from_hex(&HEX.to_uppercase())
+        .unwrap()
+        .equals(&name));
+}
+
+#[wasm_bindgen_test]
+fn invalid_hex() {
+    assert!(XorName::from_hex(&HEX[1..]).is_err());
+    assert!(XorName::from_hex(&HEX[2..]).is_err());
+    assert!(XorName::from_hex(&[HEX, "00"].concat()).is_err());
+    assert!(XorName::from_hex(&["+f", &HEX[2..]].concat()).is_err());
+    assert!(XorName::from_hex(&["0g", &HEX[2..]].concat()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn bytes() {
+    let name = XorName::from_bytes(&[7; 32]).unwrap();
+    assert_eq!(name.to_bytes(), [7; 32]);
+    assert!(XorName::from_bytes(&[7; 31]).is_err());
+    assert!(XorName::from_bytes(&[7; 33]).is_err());
+    assert!(!XorName::random().equals(&XorName::random()));
+}
+
+#[wasm_bindgen_test]
+fn distance() {
+    let target = XorName::from_bytes(&[0; 32]).unwrap();
+    let near = XorName::from_bytes(&[1; 32]).unwrap();
+    let far = XorName::from_bytes(&[2; 32]).unwrap();
+    assert_eq!(target.cmp_distance(&near, &far), -1);
+    assert_eq!(target.cmp_distance(&far, &near), 1);
+    assert_eq!(target.cmp_distance(&near, &near), 0);
+}
+
+#[wasm_bindgen_test]
+fn prefixes() {
+    let prefix = Prefix::parse("101").unwrap();
+    assert_eq!(prefix.bit_count(), 3);
+    assert_eq!(prefix.to_js_string(), "101");
+    assert!(Prefix::parse("102").is_err());
+
+    assert!(prefix.matches(&prefix.lower_bound()));
+    assert!(prefix.matches(&prefix.upper_bound()));
+    assert!(!prefix.matches(&prefix.sibling().lower_bound()));
+    assert!(prefix.is_neighbour(&prefix.sibling()));
+    assert!(prefix.is_compatible(&prefix.pushed(true)));
+    assert!(prefix.pushed(false).popped().equals(&prefix));
+    assert!(Prefix::new(3, &prefix.name()).equals(&prefix));
+}